@@ -5,8 +5,19 @@ use macroquad::prelude::BLACK;
 
 use super::EditorButtons;
 use super::EditorElements;
+use super::EditorGroup;
 use super::EditorHelps;
+use super::EditorInput;
+use super::EditorInspector;
+use super::EditorLayer;
+use super::EditorMetrics;
+use super::EditorPicker;
+use super::EditorPivot;
+use super::EditorRepeat;
 use super::EditorState;
+use super::EditorSymmetry;
+use super::EditorTransform;
+use super::EditorVertex;
 
 pub struct Editor {
     color: Color,
@@ -24,15 +35,40 @@ impl Editor {
         loop {
             clear_background(self.color.with_alpha(0.5));
 
+            // Before the input, so the key closing it does not also leave a group.
+            EditorGroup::actions(&mut self.state);
+            EditorInput::actions(&mut self.state);
+
             EditorButtons::actions(&mut self.state);
             EditorButtons::draw(&mut self.state);
 
+            EditorTransform::actions(&mut self.state);
+            EditorVertex::actions(&mut self.state);
             EditorElements::actions(&mut self.state);
             EditorElements::draw(&mut self.state);
+            EditorSymmetry::actions(&mut self.state);
+            EditorSymmetry::draw(&mut self.state);
+            EditorPivot::draw(&mut self.state);
+            EditorGroup::draw(&mut self.state);
+            EditorRepeat::draw(&mut self.state);
+            EditorTransform::draw(&mut self.state);
+            EditorVertex::draw(&mut self.state);
+
+            EditorPicker::actions(&mut self.state);
+            EditorPicker::draw(&mut self.state);
+
+            EditorInspector::actions(&mut self.state);
+            EditorInspector::draw(&mut self.state);
+
+            EditorLayer::actions(&mut self.state);
+            EditorLayer::draw(&mut self.state);
+            EditorMetrics::draw(&mut self.state);
 
             EditorHelps::actions(&mut self.state);
             EditorHelps::draw(&mut self.state);
 
+            EditorInput::draw(&mut self.state);
+
             next_frame().await;
         }
     }
@@ -0,0 +1,200 @@
+use macroquad::prelude::screen_height;
+use macroquad::prelude::screen_width;
+use macroquad::prelude::Rect;
+use macroquad::prelude::Vec2;
+
+use super::EditorGroup;
+use super::EditorState;
+use super::DISPLAY_SIZE;
+use super::DISPLAY_SIZE_HD;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorAligns {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// Lines up the horizontal centers, on one vertical line.
+    CenterX,
+    /// Lines up the vertical centers, on one horizontal line.
+    CenterY,
+    /// Spaces the elements evenly from left to right.
+    DistributeX,
+    /// Spaces the elements evenly from top to bottom.
+    DistributeY,
+}
+
+pub struct EditorAlign {}
+
+impl EditorAlign {
+    /// Display frame picked with `W`, the SD one unless the HD one is chosen.
+    pub fn frame(state: &EditorState) -> Rect {
+        let center = Vec2::new(screen_width(), screen_height()) / 2.0;
+        let size = if state.display_hd {
+            DISPLAY_SIZE_HD
+        } else {
+            DISPLAY_SIZE
+        };
+        Rect::new(
+            center.x - size.x / 2.0,
+            center.y - size.y / 2.0,
+            size.x,
+            size.y,
+        )
+    }
+
+    /// Selected elements in the units they are picked in, a group moving as one, with their
+    /// combined bounds.
+    fn items(state: &EditorState) -> Vec<(Vec<usize>, Rect)> {
+        let mut items: Vec<(Option<usize>, Vec<usize>, Rect)> = Vec::new();
+        for &i in state.selection.iter() {
+            let unit = EditorGroup::unit(state, i);
+            let bounds = state.stack[i].value.bounds();
+            match items
+                .iter_mut()
+                .find(|item| unit.is_some() && item.0 == unit)
+            {
+                Some(item) => {
+                    item.1.push(i);
+                    item.2 = item.2.combine_with(bounds);
+                }
+                None => items.push((unit, vec![i], bounds)),
+            }
+        }
+        items
+            .into_iter()
+            .map(|(_, i, bounds)| (i, bounds))
+            .collect()
+    }
+
+    /// Aligns the selection to its own bounds, or to the display frame when `display` is set or
+    /// only one element or group is selected.
+    pub fn align(state: &mut EditorState, align: EditorAligns, display: bool) {
+        let Some(bounds) = state.selection_bounds() else {
+            return;
+        };
+        let items = EditorAlign::items(state);
+        let target = if display || items.len() == 1 {
+            EditorAlign::frame(state)
+        } else {
+            bounds
+        };
+        if matches!(align, EditorAligns::DistributeX | EditorAligns::DistributeY) {
+            return EditorAlign::distribute(state, items, align);
+        }
+        state.save();
+        for (elements, bounds) in items {
+            let offset = match align {
+                EditorAligns::Left => Vec2::new(target.left() - bounds.left(), 0.0),
+                EditorAligns::Right => Vec2::new(target.right() - bounds.right(), 0.0),
+                EditorAligns::Top => Vec2::new(0.0, target.top() - bounds.top()),
+                EditorAligns::Bottom => Vec2::new(0.0, target.bottom() - bounds.bottom()),
+                EditorAligns::CenterX => Vec2::new(target.center().x - bounds.center().x, 0.0),
+                EditorAligns::CenterY => Vec2::new(0.0, target.center().y - bounds.center().y),
+                EditorAligns::DistributeX | EditorAligns::DistributeY => Vec2::ZERO,
+            };
+            for i in elements {
+                state.stack[i].value.translate(offset);
+            }
+        }
+    }
+
+    /// Keeps the outermost items in place and makes the gaps between the others equal.
+    fn distribute(state: &mut EditorState, mut list: Vec<(Vec<usize>, Rect)>, align: EditorAligns) {
+        if list.len() < 3 {
+            return;
+        }
+        let axis = if align == EditorAligns::DistributeX {
+            Vec2::X
+        } else {
+            Vec2::Y
+        };
+        list.sort_by(|(_, a), (_, b)| a.center().dot(axis).total_cmp(&b.center().dot(axis)));
+
+        let start = list[0].1.point().dot(axis);
+        let end = (list[list.len() - 1].1.point() + list[list.len() - 1].1.size()).dot(axis);
+        let size: f32 = list.iter().map(|(_, i)| i.size().dot(axis)).sum();
+        let gap = (end - start - size) / (list.len() - 1) as f32;
+
+        state.save();
+        let mut position = start;
+        for (elements, bounds) in list {
+            let offset = position - bounds.point().dot(axis);
+            for i in elements {
+                state.stack[i].value.translate(axis * offset);
+            }
+            position += bounds.size().dot(axis) + gap;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+
+    use super::EditorAlign;
+    use super::EditorAligns;
+    use super::EditorGroup;
+    use super::EditorState;
+    use crate::editor::EditorElement;
+    use crate::editor::EditorValues;
+
+    fn rectangle(state: &mut EditorState, x: f32, group: Option<usize>) {
+        let value = EditorValues::Rectangle {
+            point: Vec2::new(x, 0.0),
+            width: 10.0,
+            height: 10.0,
+            rotation: 0.0,
+        };
+        let mut element = EditorElement::new(value, state.style());
+        element.group = group;
+        state.stack.push(element);
+    }
+
+    fn left(state: &EditorState, i: usize) -> f32 {
+        state.stack[i].value.bounds().left()
+    }
+
+    #[test]
+    fn groups_align_as_one() {
+        let mut state = EditorState::new();
+        state.groups.push(EditorGroup {
+            name: "pair".to_string(),
+            parent: None,
+        });
+        rectangle(&mut state, 0.0, None);
+        rectangle(&mut state, 50.0, Some(0));
+        rectangle(&mut state, 70.0, Some(0));
+        state.selection = vec![0, 1, 2];
+        EditorAlign::align(&mut state, EditorAligns::Right, false);
+        assert_eq!(
+            [left(&state, 0), left(&state, 1), left(&state, 2)],
+            [70.0, 50.0, 70.0]
+        );
+    }
+
+    #[test]
+    fn groups_distribute_as_one() {
+        let mut state = EditorState::new();
+        state.groups.push(EditorGroup {
+            name: "pair".to_string(),
+            parent: None,
+        });
+        rectangle(&mut state, 0.0, None);
+        rectangle(&mut state, 20.0, Some(0));
+        rectangle(&mut state, 40.0, Some(0));
+        rectangle(&mut state, 100.0, None);
+        state.selection = vec![0, 1, 2, 3];
+        EditorAlign::align(&mut state, EditorAligns::DistributeX, false);
+        // The group spans 30, leaving two gaps of 30 between 0 and 110.
+        assert_eq!(
+            [
+                left(&state, 0),
+                left(&state, 1),
+                left(&state, 2),
+                left(&state, 3)
+            ],
+            [0.0, 40.0, 60.0, 100.0]
+        );
+    }
+}
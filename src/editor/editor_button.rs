@@ -38,6 +38,8 @@ impl EditorButton {
         let left_top_buttons: Vec<EditorButton> = [
             EditorButtons::Grid,
             EditorButtons::Snap,
+            EditorButtons::Vertex,
+            EditorButtons::Hitbox,
             EditorButtons::Redo,
             EditorButtons::Undo,
         ]
@@ -56,12 +58,12 @@ impl EditorButton {
             // EditorButtons::Arc,
             // EditorButtons::Poly,
             EditorButtons::Circle,
-            EditorButtons::CircleLine,
             EditorButtons::Ellipse,
-            EditorButtons::EllipseLine,
             EditorButtons::Rectangle,
             EditorButtons::Triangle,
             EditorButtons::Hexagon,
+            EditorButtons::Polygon,
+            EditorButtons::Marker,
         ]
         .iter()
         .rev()
@@ -73,25 +75,36 @@ impl EditorButton {
         })
         .collect();
 
-        let right_top_buttons: Vec<EditorButton> = [EditorButtons::Help]
-            .iter()
-            .rev()
-            .scan((width, 20.0), |(x, y), &button| {
-                let dimensions = button.dimensions();
-                *x -= dimensions.width + 10.0;
-                Some(EditorButton::new(button, *x, *y, BUTTON_SIZE))
-            })
-            .collect();
+        let right_top_buttons: Vec<EditorButton> = [
+            EditorButtons::Metrics,
+            EditorButtons::Layers,
+            EditorButtons::Help,
+        ]
+        .iter()
+        .rev()
+        .scan((width, 20.0), |(x, y), &button| {
+            let dimensions = button.dimensions();
+            *x -= dimensions.width + 10.0;
+            Some(EditorButton::new(button, *x, *y, BUTTON_SIZE))
+        })
+        .collect();
 
-        let right_bottom_buttons: Vec<EditorButton> = [EditorButtons::Color]
-            .iter()
-            .rev()
-            .scan((width, height - 10.0), |(x, y), &button| {
-                let dimensions = button.dimensions();
-                *x -= dimensions.width + 10.0;
-                Some(EditorButton::new(button, *x, *y, BUTTON_SIZE))
-            })
-            .collect();
+        let right_bottom_buttons: Vec<EditorButton> = [
+            EditorButtons::ThicknessDown,
+            EditorButtons::Thickness,
+            EditorButtons::ThicknessUp,
+            EditorButtons::Fill,
+            EditorButtons::Stroke,
+            EditorButtons::Color,
+        ]
+        .iter()
+        .rev()
+        .scan((width, height - 10.0), |(x, y), &button| {
+            let dimensions = button.dimensions();
+            *x -= dimensions.width + 10.0;
+            Some(EditorButton::new(button, *x, *y, BUTTON_SIZE))
+        })
+        .collect();
 
         [
             left_top_buttons,
@@ -5,7 +5,6 @@ use macroquad::prelude::is_key_released;
 use macroquad::prelude::is_mouse_button_pressed;
 use macroquad::prelude::measure_text;
 use macroquad::prelude::mouse_position;
-use macroquad::prelude::Color;
 use macroquad::prelude::KeyCode;
 use macroquad::prelude::MouseButton;
 use macroquad::prelude::TextDimensions;
@@ -14,27 +13,22 @@ use macroquad::prelude::DARKGRAY;
 use macroquad::prelude::GRAY;
 use macroquad::prelude::GREEN;
 use macroquad::prelude::LIGHTGRAY;
-use macroquad::prelude::ORANGE;
-use macroquad::prelude::PINK;
-use macroquad::prelude::PURPLE;
-use macroquad::prelude::RED;
-use macroquad::prelude::YELLOW;
 
+use super::EditorAlign;
+use super::EditorAligns;
 use super::EditorButton;
+use super::EditorClipboard;
 use super::EditorElements;
+use super::EditorGroup;
+use super::EditorHull;
+use super::EditorInput;
+use super::EditorInputs;
+use super::EditorPivot;
+use super::EditorRepeats;
 use super::EditorState;
-
-pub const COLORS: [Color; 9] = [
-    DARKGRAY.with_alpha(0.5),
-    GRAY.with_alpha(0.5),
-    GREEN.with_alpha(0.5),
-    PINK.with_alpha(0.5),
-    LIGHTGRAY.with_alpha(0.5),
-    ORANGE.with_alpha(0.5),
-    PURPLE.with_alpha(0.5),
-    RED.with_alpha(0.5),
-    YELLOW.with_alpha(0.5),
-];
+use super::EditorSymmetry;
+use super::SIZE_GRID;
+use super::THICKNESS_STEP;
 
 pub const BUTTON_SIZE: f32 = 21.0;
 
@@ -43,25 +37,31 @@ pub enum EditorButtons {
     Undo,
     Redo,
     Help,
+    Layers,
+    Metrics,
     Grid,
     Snap,
+    Vertex,
+    Hitbox,
+    Fill,
+    Stroke,
     Color,
-    // Thickness,
+    ThicknessDown,
+    Thickness,
+    ThicknessUp,
     // Zoom,
     // ZoomIn,
     // ZoonOut,
     Line,
     // Arc,
     // Poly,
-    // PolyLine,
     Circle,
-    CircleLine,
     Ellipse,
-    EllipseLine,
     Rectangle,
-    // RectangleLine,
     Triangle,
     Hexagon,
+    Polygon,
+    Marker,
 }
 
 impl EditorButtons {
@@ -70,21 +70,30 @@ impl EditorButtons {
             EditorButtons::Undo => "UNDO",
             EditorButtons::Redo => "REDO",
             EditorButtons::Help => "HELP",
+            EditorButtons::Layers => "LAYERS",
+            EditorButtons::Metrics => "METRICS",
             EditorButtons::Grid => "GRID",
             EditorButtons::Snap => "SNAP",
+            EditorButtons::Vertex => "VERTEX",
+            EditorButtons::Hitbox => "HITBOX",
+            EditorButtons::Fill => "FILL",
+            EditorButtons::Stroke => "STROKE",
             EditorButtons::Color => "COLOR",
+            EditorButtons::ThicknessDown => "-",
+            EditorButtons::Thickness => "THICKNESS",
+            EditorButtons::ThicknessUp => "+",
             // EditorButtons::ZoomIn => "ZOOM_IN",
             // EditorButtons::ZoomOut => "ZOOM_OUT",
             EditorButtons::Line => "LINE",
             // EditorButtons::Arc => "ARC",
             // EditorButtons::Poly => "POLY",
             EditorButtons::Circle => "CIRCLE",
-            EditorButtons::CircleLine => "CIRCLE LINE",
             EditorButtons::Ellipse => "ELLIPSE",
-            EditorButtons::EllipseLine => "ELLIPSE LINE",
             EditorButtons::Rectangle => "RECTANGLE",
             EditorButtons::Triangle => "TRIANGLE",
             EditorButtons::Hexagon => "HEXAGON",
+            EditorButtons::Polygon => "POLYGON",
+            EditorButtons::Marker => "MARKER",
         }
     }
     pub fn dimensions(&self) -> TextDimensions {
@@ -128,6 +137,20 @@ impl EditorButtons {
                         GRAY
                     }
                 }
+                EditorButtons::Layers => {
+                    if is_position || state.layer_panel {
+                        GREEN
+                    } else {
+                        GRAY
+                    }
+                }
+                EditorButtons::Metrics => {
+                    if is_position || state.metrics_panel {
+                        GREEN
+                    } else {
+                        GRAY
+                    }
+                }
                 EditorButtons::Snap => {
                     if is_position || state.snap {
                         GREEN
@@ -135,6 +158,20 @@ impl EditorButtons {
                         GRAY
                     }
                 }
+                EditorButtons::Hitbox => {
+                    if is_position || state.hitbox {
+                        GREEN
+                    } else {
+                        GRAY
+                    }
+                }
+                EditorButtons::Vertex => {
+                    if is_position || state.vertex && !state.draw {
+                        GREEN
+                    } else {
+                        GRAY
+                    }
+                }
                 EditorButtons::Grid => {
                     if is_position || state.grid >= 1 {
                         GREEN
@@ -143,33 +180,72 @@ impl EditorButtons {
                     }
                 }
                 EditorButtons::Circle
-                | EditorButtons::CircleLine
                 | EditorButtons::Ellipse
-                | EditorButtons::EllipseLine
                 | EditorButtons::Line
                 // | EditorButtons::Arc
                 // | EditorButtons::Poly
                 | EditorButtons::Rectangle
                 | EditorButtons::Triangle
-                | EditorButtons::Hexagon => {
+                | EditorButtons::Hexagon
+                | EditorButtons::Polygon
+                | EditorButtons::Marker => {
                     if is_position || i.button == EditorButtons::from(state.element) && state.draw {
                         GREEN
                     } else {
                         GRAY
                     }
                 }
+                EditorButtons::Fill => {
+                    if is_position {
+                        LIGHTGRAY
+                    } else if state.element_fill {
+                        state.element_color
+                    } else {
+                        DARKGRAY
+                    }
+                }
+                EditorButtons::Stroke => {
+                    if is_position {
+                        LIGHTGRAY
+                    } else if state.element_stroke {
+                        state.element_stroke_color
+                    } else {
+                        DARKGRAY
+                    }
+                }
                 EditorButtons::Color => {
                     if is_position {
                         LIGHTGRAY
+                    } else if state.picker {
+                        GREEN
                     } else {
                         state.element_color
                     }
                 }
+                EditorButtons::ThicknessDown | EditorButtons::ThicknessUp => {
+                    if is_position {
+                        LIGHTGRAY
+                    } else {
+                        GRAY
+                    }
+                }
+                EditorButtons::Thickness => {
+                    let thickness = format!("{:.1}", state.element_thickness);
+                    draw_text(&thickness, i.x, i.y - i.size, i.size * 0.8, GRAY);
+                    if is_position {
+                        LIGHTGRAY
+                    } else {
+                        GRAY
+                    }
+                }
             };
             draw_text(text, i.x, i.y, i.size, color);
         }
     }
     pub fn actions(state: &mut EditorState) {
+        if state.input.is_some() {
+            return;
+        }
         if is_key_pressed(KeyCode::Z) && is_key_down(KeyCode::LeftSuper) {
             state.undo();
         }
@@ -187,6 +263,22 @@ impl EditorButtons {
                 state.grid += 1;
             }
         }
+        // Opened here, after the input has read this frame's characters, so the key is not typed
+        // into the name.
+        if is_key_pressed(KeyCode::G) && !is_key_down(KeyCode::LeftSuper) {
+            if is_key_down(KeyCode::LeftShift) {
+                EditorGroup::ungroup(state);
+            } else if let Some(id) = EditorGroup::group(state) {
+                let text = state.groups[id].name.clone();
+                state.input = Some(EditorInput::new(EditorInputs::Group(id), text));
+            }
+        }
+        if is_key_pressed(KeyCode::N) {
+            if let Some(id) = EditorGroup::selected(state) {
+                let text = state.groups[id].name.clone();
+                state.input = Some(EditorInput::new(EditorInputs::Group(id), text));
+            }
+        }
 
         if is_key_pressed(KeyCode::Key1) && is_key_down(KeyCode::LeftSuper) {
             state.element = EditorElements::Line;
@@ -201,9 +293,123 @@ impl EditorButtons {
         if is_key_pressed(KeyCode::E) {
             state.export();
         }
+        let command = is_key_down(KeyCode::LeftSuper) || is_key_down(KeyCode::LeftControl);
+        if command && is_key_pressed(KeyCode::C) {
+            EditorClipboard::copy(state);
+        }
+        if command && is_key_pressed(KeyCode::V) {
+            EditorClipboard::paste(state, is_key_down(KeyCode::LeftShift));
+        }
+        if command && is_key_pressed(KeyCode::D) {
+            EditorClipboard::duplicate(state);
+        }
+        if is_key_pressed(KeyCode::C) && !command {
+            state.picker = !state.picker;
+        }
+        if is_key_pressed(KeyCode::T) && !state.draw && !state.selection.is_empty() {
+            state.input = Some(EditorInput::new(EditorInputs::Rotation, String::new()));
+        }
+        if is_key_pressed(KeyCode::V) && !command {
+            state.vertex();
+        }
+        if is_key_pressed(KeyCode::K) {
+            state.hitbox = !state.hitbox;
+        }
+        if is_key_pressed(KeyCode::U) {
+            EditorHull::create(state, is_key_down(KeyCode::LeftShift));
+        }
+        if is_key_pressed(KeyCode::F) && !is_key_down(KeyCode::LeftSuper) {
+            let horizontal = !is_key_down(KeyCode::LeftShift);
+            state.flip(horizontal, is_key_down(KeyCode::LeftAlt));
+        }
+        if is_key_pressed(KeyCode::W) {
+            state.display_hd = !state.display_hd;
+        }
+        let display = is_key_down(KeyCode::LeftAlt);
+        if !is_key_down(KeyCode::LeftSuper) {
+            let step = if is_key_down(KeyCode::LeftShift) {
+                SIZE_GRID
+            } else {
+                1.0
+            };
+            for (key, offset) in [
+                (KeyCode::Left, Vec2::new(-step, 0.0)),
+                (KeyCode::Right, Vec2::new(step, 0.0)),
+                (KeyCode::Up, Vec2::new(0.0, -step)),
+                (KeyCode::Down, Vec2::new(0.0, step)),
+            ] {
+                if is_key_pressed(key) {
+                    state.translate(offset);
+                }
+            }
+        } else {
+            for (key, align) in [
+                (KeyCode::Left, EditorAligns::Left),
+                (KeyCode::Right, EditorAligns::Right),
+                (KeyCode::Up, EditorAligns::Top),
+                (KeyCode::Down, EditorAligns::Bottom),
+            ] {
+                if is_key_pressed(key) {
+                    EditorAlign::align(state, align, display);
+                }
+            }
+        }
+        if is_key_pressed(KeyCode::A) {
+            let align = if is_key_down(KeyCode::LeftShift) {
+                EditorAligns::CenterY
+            } else {
+                EditorAligns::CenterX
+            };
+            EditorAlign::align(state, align, display);
+        }
+        if is_key_pressed(KeyCode::D) && !command {
+            let align = if is_key_down(KeyCode::LeftShift) {
+                EditorAligns::DistributeY
+            } else {
+                EditorAligns::DistributeX
+            };
+            EditorAlign::align(state, align, display);
+        }
+        for (key, forward) in [(KeyCode::RightBracket, true), (KeyCode::LeftBracket, false)] {
+            if is_key_pressed(key) {
+                state.arrange(forward, is_key_down(KeyCode::LeftShift));
+            }
+        }
+        if is_key_pressed(KeyCode::P) && !state.draw && !state.selection.is_empty() {
+            let repeat = if is_key_down(KeyCode::LeftShift) {
+                state.repeat_pivot = state.position();
+                EditorRepeats::Radial
+            } else {
+                EditorRepeats::Linear
+            };
+            let input = EditorInputs::Repeat(repeat);
+            state.input = Some(EditorInput::new(input, repeat.default()));
+        }
+        if is_key_pressed(KeyCode::O) {
+            if is_key_down(KeyCode::LeftShift) {
+                EditorPivot::place(state);
+            } else {
+                EditorPivot::next(state);
+            }
+        }
+        if is_key_pressed(KeyCode::M) {
+            EditorSymmetry::next(state);
+        }
+        if is_key_pressed(KeyCode::L) {
+            state.connect = !state.connect;
+        }
+        if is_key_pressed(KeyCode::R) && !is_key_down(KeyCode::LeftSuper) {
+            if is_key_down(KeyCode::LeftShift) {
+                if let Some(color) = state.selection_color() {
+                    state.replace(color, state.picker_color());
+                }
+            } else {
+                state.recolor();
+            }
+        }
 
         if is_mouse_button_pressed(MouseButton::Left) {
-            if let Some(button) = EditorButton::find().take() {
+            if let Some(button) = EditorButton::find() {
                 if [
                     EditorButtons::Ellipse,
                     EditorButtons::Line,
@@ -211,14 +417,21 @@ impl EditorButtons {
                     EditorButtons::Rectangle,
                     EditorButtons::Circle,
                     EditorButtons::Hexagon,
+                    EditorButtons::Polygon,
+                    EditorButtons::Marker,
                 ]
                 .contains(&button.button)
                 {
+                    state.polygon.clear();
                     if state.button == Some(button.button) {
                         state.draw = !state.draw;
                     } else {
                         state.draw = true;
                     }
+                    if state.draw {
+                        state.selection.clear();
+                        state.vertex = false;
+                    }
                 }
                 match button.button {
                     EditorButtons::Undo => {
@@ -245,10 +458,6 @@ impl EditorButtons {
                         state.button = Some(EditorButtons::Ellipse);
                         state.element = EditorElements::Ellipse;
                     }
-                    EditorButtons::EllipseLine => {
-                        state.button = Some(EditorButtons::EllipseLine);
-                        state.element = EditorElements::EllipseLine;
-                    }
                     EditorButtons::Rectangle => {
                         state.button = Some(EditorButtons::Rectangle);
                         state.element = EditorElements::Rectangle;
@@ -261,6 +470,14 @@ impl EditorButtons {
                         state.button = Some(EditorButtons::Hexagon);
                         state.element = EditorElements::Hexagon;
                     }
+                    EditorButtons::Polygon => {
+                        state.button = Some(EditorButtons::Polygon);
+                        state.element = EditorElements::Polygon;
+                    }
+                    EditorButtons::Marker => {
+                        state.button = Some(EditorButtons::Marker);
+                        state.element = EditorElements::Marker;
+                    }
                     EditorButtons::Line => {
                         state.button = Some(EditorButtons::Line);
                         state.element = EditorElements::Line;
@@ -269,10 +486,6 @@ impl EditorButtons {
                         state.button = Some(EditorButtons::Circle);
                         state.element = EditorElements::Circle;
                     }
-                    EditorButtons::CircleLine => {
-                        state.button = Some(EditorButtons::CircleLine);
-                        state.element = EditorElements::CircleLine;
-                    }
                     EditorButtons::Grid => {
                         state.button = Some(EditorButtons::Grid);
                         if state.grid > 2 {
@@ -285,13 +498,41 @@ impl EditorButtons {
                         state.button = Some(EditorButtons::Snap);
                         state.snap = !state.snap;
                     }
-                    EditorButtons::Color => {
-                        if state.element_color_index >= COLORS.len() - 1 {
-                            state.element_color_index = 0;
-                        } else {
-                            state.element_color_index += 1;
+                    EditorButtons::Vertex => {
+                        state.vertex();
+                    }
+                    EditorButtons::Hitbox => {
+                        state.hitbox = !state.hitbox;
+                    }
+                    EditorButtons::Layers => {
+                        state.layer_panel = !state.layer_panel;
+                    }
+                    EditorButtons::Metrics => {
+                        state.metrics_panel = !state.metrics_panel;
+                    }
+                    EditorButtons::Fill => {
+                        state.element_fill = !state.element_fill;
+                        if !state.element_fill {
+                            state.element_stroke = true;
                         }
-                        state.element_color = COLORS[state.element_color_index];
+                    }
+                    EditorButtons::Stroke => {
+                        state.element_stroke = !state.element_stroke;
+                        if !state.element_stroke {
+                            state.element_fill = true;
+                        }
+                    }
+                    EditorButtons::ThicknessDown => {
+                        state.thickness(-THICKNESS_STEP);
+                    }
+                    EditorButtons::Thickness => {
+                        state.thickness(1.0 - state.element_thickness);
+                    }
+                    EditorButtons::ThicknessUp => {
+                        state.thickness(THICKNESS_STEP);
+                    }
+                    EditorButtons::Color => {
+                        state.picker = !state.picker;
                     }
                 }
             }
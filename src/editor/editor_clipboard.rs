@@ -0,0 +1,322 @@
+use macroquad::prelude::mouse_position;
+use macroquad::prelude::Color;
+use macroquad::prelude::Vec2;
+use miniquad::window::clipboard_get;
+use miniquad::window::clipboard_set;
+
+use super::EditorElement;
+use super::EditorExport;
+use super::EditorGroup;
+use super::EditorJson;
+use super::EditorPalette;
+use super::EditorState;
+use super::EditorStyle;
+use super::EditorTag;
+use super::EditorValues;
+use super::SIZE_GRID;
+use super::TAGS_MAX;
+
+/// Element read from the clipboard, with its group path, name and tags still as text.
+pub struct EditorClip {
+    pub element: EditorElement,
+    pub group: Vec<String>,
+    pub name: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Copies elements as JSON text, kept in the editor and put on the system clipboard so shapes
+/// can move between editor instances. Groups travel as paths of names and are recreated on
+/// paste; symmetry links are dropped.
+pub struct EditorClipboard {}
+
+impl EditorClipboard {
+    fn point(point: Vec2) -> EditorJson {
+        let x = EditorJson::Number(point.x as f64);
+        let y = EditorJson::Number(point.y as f64);
+        EditorJson::Array(vec![x, y])
+    }
+
+    fn color(color: Option<Color>) -> EditorJson {
+        match color {
+            Some(color) => EditorJson::String(EditorPalette::hex(color)),
+            None => EditorJson::Null,
+        }
+    }
+
+    fn json(state: &EditorState, element: &EditorElement) -> EditorJson {
+        let number = |value: f32| EditorJson::Number(value as f64);
+        let mut items = match element.value {
+            EditorValues::Line { point_a, point_b } => vec![
+                ("type", EditorJson::String("line".to_string())),
+                ("point_a", EditorClipboard::point(point_a)),
+                ("point_b", EditorClipboard::point(point_b)),
+            ],
+            EditorValues::Circle { center, radius } => vec![
+                ("type", EditorJson::String("circle".to_string())),
+                ("center", EditorClipboard::point(center)),
+                ("radius", number(radius)),
+            ],
+            EditorValues::Ellipse {
+                center,
+                width,
+                height,
+                rotation,
+            } => vec![
+                ("type", EditorJson::String("ellipse".to_string())),
+                ("center", EditorClipboard::point(center)),
+                ("width", number(width)),
+                ("height", number(height)),
+                ("rotation", number(rotation)),
+            ],
+            EditorValues::Rectangle {
+                point,
+                width,
+                height,
+                rotation,
+            } => vec![
+                ("type", EditorJson::String("rectangle".to_string())),
+                ("point", EditorClipboard::point(point)),
+                ("width", number(width)),
+                ("height", number(height)),
+                ("rotation", number(rotation)),
+            ],
+            EditorValues::Triangle {
+                point_a,
+                point_b,
+                point_c,
+            } => vec![
+                ("type", EditorJson::String("triangle".to_string())),
+                ("point_a", EditorClipboard::point(point_a)),
+                ("point_b", EditorClipboard::point(point_b)),
+                ("point_c", EditorClipboard::point(point_c)),
+            ],
+            EditorValues::Hexagon {
+                center,
+                radius,
+                vertical,
+            } => vec![
+                ("type", EditorJson::String("hexagon".to_string())),
+                ("center", EditorClipboard::point(center)),
+                ("radius", number(radius)),
+                ("vertical", EditorJson::Bool(vertical)),
+            ],
+            EditorValues::Polygon { .. } => vec![
+                ("type", EditorJson::String("polygon".to_string())),
+                (
+                    "points",
+                    EditorJson::Array(
+                        element
+                            .value
+                            .points()
+                            .into_iter()
+                            .map(EditorClipboard::point)
+                            .collect(),
+                    ),
+                ),
+            ],
+            EditorValues::Marker { point } => vec![
+                ("type", EditorJson::String("marker".to_string())),
+                ("point", EditorClipboard::point(point)),
+            ],
+        };
+        items.push(("fill", EditorClipboard::color(element.style.fill)));
+        items.push(("stroke", EditorClipboard::color(element.style.stroke)));
+        items.push(("thickness", number(element.style.thickness)));
+        items.push(("hitbox", EditorJson::Bool(element.hitbox)));
+
+        // Only the groups inside the entered one, outermost first.
+        let path = EditorGroup::path(state, element.group);
+        let end = state
+            .group
+            .and_then(|group| path.iter().position(|&i| i == group))
+            .unwrap_or(path.len());
+        let names = path[..end]
+            .iter()
+            .rev()
+            .map(|&i| EditorJson::String(state.groups[i].name.clone()));
+        items.push(("group", EditorJson::Array(names.collect())));
+
+        let name = EditorTag::name(state, element).map(|i| EditorJson::String(i.to_string()));
+        items.push(("name", name.unwrap_or(EditorJson::Null)));
+        let tags = EditorTag::tags(state, element)
+            .into_iter()
+            .map(|i| EditorJson::String(i.to_string()));
+        items.push(("tags", EditorJson::Array(tags.collect())));
+
+        let items = items.into_iter().map(|(k, v)| (k.to_string(), v));
+        EditorJson::Object(items.collect())
+    }
+
+    fn element(json: &EditorJson) -> Option<EditorClip> {
+        let number = |key: &str| json.get(key).and_then(EditorJson::as_f32);
+        let point = |key: &str| {
+            let point = json.get(key)?.as_array()?;
+            Some(Vec2::new(point.first()?.as_f32()?, point.get(1)?.as_f32()?))
+        };
+        let value = match json.get("type")?.as_str()? {
+            "line" => EditorValues::Line {
+                point_a: point("point_a")?,
+                point_b: point("point_b")?,
+            },
+            "circle" => EditorValues::Circle {
+                center: point("center")?,
+                radius: number("radius")?,
+            },
+            "ellipse" => EditorValues::Ellipse {
+                center: point("center")?,
+                width: number("width")?,
+                height: number("height")?,
+                rotation: number("rotation")?,
+            },
+            "rectangle" => EditorValues::Rectangle {
+                point: point("point")?,
+                width: number("width")?,
+                height: number("height")?,
+                rotation: number("rotation")?,
+            },
+            "triangle" => EditorValues::Triangle {
+                point_a: point("point_a")?,
+                point_b: point("point_b")?,
+                point_c: point("point_c")?,
+            },
+            "hexagon" => EditorValues::Hexagon {
+                center: point("center")?,
+                radius: number("radius")?,
+                vertical: json.get("vertical")?.as_bool()?,
+            },
+            "polygon" => {
+                let points = json.get("points")?.as_array()?;
+                let points = points
+                    .iter()
+                    .map(|i| {
+                        let point = i.as_array()?;
+                        Some(Vec2::new(point.first()?.as_f32()?, point.get(1)?.as_f32()?))
+                    })
+                    .collect::<Option<Vec<Vec2>>>()?;
+                match EditorValues::polygon(&points) {
+                    value if value.points().len() >= 3 => value,
+                    _ => return None,
+                }
+            }
+            "marker" => EditorValues::Marker {
+                point: point("point")?,
+            },
+            _ => return None,
+        };
+        let color = |key: &str| json.get(key)?.as_str().and_then(EditorPalette::parse);
+        let style = EditorStyle {
+            fill: color("fill"),
+            stroke: color("stroke"),
+            thickness: number("thickness")?,
+        };
+        // Group names from another source must still be valid identifiers.
+        let identifiers = |key: &str| {
+            json.get(key)
+                .and_then(EditorJson::as_array)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|i| i.as_str().and_then(EditorExport::identifier))
+                .collect()
+        };
+        let mut element = EditorElement::new(value, style);
+        element.hitbox = json.get("hitbox").and_then(EditorJson::as_bool) == Some(true)
+            && element.value.closed();
+        Some(EditorClip {
+            element,
+            group: identifiers("group"),
+            name: json
+                .get("name")
+                .and_then(EditorJson::as_str)
+                .map(str::to_string),
+            tags: identifiers("tags"),
+        })
+    }
+
+    pub fn serialize(state: &EditorState, indices: &[usize]) -> String {
+        let elements = indices
+            .iter()
+            .map(|&i| EditorClipboard::json(state, &state.stack[i]));
+        let json = EditorJson::Object(vec![(
+            "elements".to_string(),
+            EditorJson::Array(elements.collect()),
+        )]);
+        json.to_string()
+    }
+
+    pub fn deserialize(text: &str) -> Option<Vec<EditorClip>> {
+        let json = EditorJson::parse(text).ok()?;
+        let elements = json.get("elements")?.as_array()?;
+        elements.iter().map(EditorClipboard::element).collect()
+    }
+
+    /// Adds the elements on the active layer as one undo step and selects them.
+    fn insert(state: &mut EditorState, elements: Vec<EditorClip>, offset: Vec2) {
+        if elements.is_empty() {
+            return;
+        }
+        state.save();
+        state.draw = false;
+        let start = state.stack.len();
+        let mut groups = Vec::new();
+        for clip in elements {
+            let mut element = clip.element;
+            element.value.translate(offset);
+            element.layer = state.layer;
+            element.group = EditorGroup::create(state, &clip.group, &mut groups);
+            element.name = clip
+                .name
+                .and_then(|i| EditorTag::parse_name(state, &i).ok())
+                .flatten();
+            // Tags past the limit are dropped, keeping the first ones.
+            let tags = clip.tags[..clip.tags.len().min(TAGS_MAX)].join(" ");
+            element.tags = EditorTag::parse_tags(state, &tags).unwrap_or_default();
+            state.stack.push(element);
+        }
+        state.selection = (start..state.stack.len()).collect();
+    }
+
+    pub fn copy(state: &mut EditorState) {
+        if state.selection.is_empty() {
+            return;
+        }
+        let text = EditorClipboard::serialize(state, &state.selection);
+        clipboard_set(&text);
+        state.clipboard = text;
+        state.clipboard_count = 0;
+    }
+
+    /// Pastes one grid step further from the copied elements on every paste, or centered on
+    /// the cursor.
+    pub fn paste(state: &mut EditorState, cursor: bool) {
+        let parse = |text: String| Some((EditorClipboard::deserialize(&text)?, text));
+        let elements = clipboard_get()
+            .and_then(parse)
+            .or_else(|| parse(state.clipboard.clone()));
+        let Some((elements, text)) = elements else {
+            return;
+        };
+        if text != state.clipboard {
+            state.clipboard = text;
+            state.clipboard_count = 0;
+        }
+        let bounds = elements
+            .iter()
+            .map(|i| i.element.value.bounds())
+            .reduce(|a, b| a.combine_with(b))
+            .unwrap_or_default();
+        state.clipboard_count += 1;
+        let offset = if cursor {
+            Vec2::from(mouse_position()) - bounds.center()
+        } else {
+            Vec2::splat(SIZE_GRID * state.clipboard_count as f32)
+        };
+        EditorClipboard::insert(state, elements, offset);
+    }
+
+    pub fn duplicate(state: &mut EditorState) {
+        let text = EditorClipboard::serialize(state, &state.selection);
+        if let Some(elements) = EditorClipboard::deserialize(&text) {
+            EditorClipboard::insert(state, elements, Vec2::ZERO);
+        }
+    }
+}
@@ -1,42 +1,46 @@
-use macroquad::prelude::draw_circle;
 use macroquad::prelude::draw_ellipse;
 use macroquad::prelude::draw_hexagon;
 use macroquad::prelude::draw_line;
 use macroquad::prelude::draw_rectangle_ex;
+use macroquad::prelude::draw_rectangle_lines_ex;
 use macroquad::prelude::draw_triangle;
+use macroquad::prelude::draw_triangle_lines;
 use macroquad::prelude::Color;
 use macroquad::prelude::DrawRectangleParams;
+use macroquad::prelude::Rect;
 use macroquad::prelude::Vec2;
+use macroquad::prelude::BLANK;
+use macroquad::prelude::WHITE;
+use macroquad::shapes::draw_circle;
 use macroquad::shapes::draw_circle_lines;
 use macroquad::shapes::draw_ellipse_lines;
 
-#[derive(Debug, Clone, Copy)]
+use super::EditorHull;
+use super::EditorLink;
+use super::SIZE_POINT;
+use super::TAGS_MAX;
+
+pub const MARKER_SIZE: f32 = 6.0;
+pub const POLYGON_MAX: usize = 8;
+pub const CURVE_SEGMENTS: usize = 16;
+pub const HITBOX_COLOR: Color = Color::new(1.0, 0.0, 0.5, 0.35);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum EditorValues {
     Line {
         point_a: Vec2,
         point_b: Vec2,
-        thickness: f32,
     },
     Circle {
         center: Vec2,
         radius: f32,
     },
-    CircleLine {
-        center: Vec2,
-        radius: f32,
-    },
     Ellipse {
         center: Vec2,
         width: f32,
         height: f32,
         rotation: f32,
     },
-    EllipseLine {
-        center: Vec2,
-        width: f32,
-        height: f32,
-        rotation: f32,
-    },
     Rectangle {
         point: Vec2,
         width: f32,
@@ -53,47 +57,534 @@ pub enum EditorValues {
         radius: f32,
         vertical: bool,
     },
+    /// Convex, with its first `count` corners in use so the value stays `Copy`.
+    Polygon {
+        corners: [Vec2; POLYGON_MAX],
+        count: usize,
+    },
+    /// Attachment point for gameplay, drawn only in the editor and exported as a `Vec2`.
+    Marker {
+        point: Vec2,
+    },
+}
+
+impl EditorValues {
+    /// Corners of a rectangle, which is anchored and rotated (in radians) at its top-left point.
+    pub fn corners(point: Vec2, width: f32, height: f32, rotation: f32) -> [Vec2; 4] {
+        let rotation = Vec2::from_angle(rotation);
+        [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(width, 0.0),
+            Vec2::new(width, height),
+            Vec2::new(0.0, height),
+        ]
+        .map(|corner| point + rotation.rotate(corner))
+    }
+
+    /// Vertices of a hexagon, matching `draw_hexagon` which points along `y` when vertical.
+    pub fn vertices(center: Vec2, radius: f32, vertical: bool) -> [Vec2; 6] {
+        let rotation = if vertical { 90.0_f32 } else { 0.0 };
+        let mut vertices = [center; 6];
+        for (i, vertex) in vertices.iter_mut().enumerate() {
+            let angle = (rotation + i as f32 * 60.0).to_radians();
+            *vertex += Vec2::from_angle(angle) * radius;
+        }
+        vertices
+    }
+
+    /// Convex polygon around `points`, cut down to `POLYGON_MAX` corners so it still covers
+    /// them. Points without a hull, like the first clicks of a new polygon, are kept as they are.
+    pub fn polygon(points: &[Vec2]) -> EditorValues {
+        let hull = match EditorHull::hull(points) {
+            hull if hull.is_empty() => points.to_vec(),
+            hull => EditorHull::reduce(hull, POLYGON_MAX),
+        };
+        let count = hull.len().min(POLYGON_MAX);
+        let mut corners = [Vec2::ZERO; POLYGON_MAX];
+        corners[..count].copy_from_slice(&hull[..count]);
+        EditorValues::Polygon { corners, count }
+    }
+
+    /// Hull of a polygon whose corners were moved one at a time, which can leave it concave.
+    /// Called once the edit is done so the corner being dragged keeps its index meanwhile.
+    pub fn convex(&mut self) {
+        if let EditorValues::Polygon { corners, count } = *self {
+            *self = EditorValues::polygon(&corners[..count]);
+        }
+    }
+
+    /// Whether the shape encloses an area, which a hitbox needs.
+    pub fn closed(&self) -> bool {
+        !matches!(
+            self,
+            EditorValues::Line { .. } | EditorValues::Marker { .. }
+        )
+    }
+
+    pub fn bounds(&self) -> Rect {
+        let points = |points: &[Vec2]| {
+            let min = points.iter().fold(Vec2::MAX, |a, &b| a.min(b));
+            let max = points.iter().fold(Vec2::MIN, |a, &b| a.max(b));
+            Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+        };
+        match *self {
+            EditorValues::Line { point_a, point_b } => points(&[point_a, point_b]),
+            EditorValues::Circle { center, radius } => Rect::new(
+                center.x - radius,
+                center.y - radius,
+                radius * 2.0,
+                radius * 2.0,
+            ),
+            EditorValues::Ellipse {
+                center,
+                width,
+                height,
+                rotation,
+            } => {
+                // Width and height are semi-axes and the rotation is in degrees, as in `draw_ellipse`.
+                let (sin, cos) = rotation.to_radians().sin_cos();
+                let x = ((width * cos).powi(2) + (height * sin).powi(2)).sqrt();
+                let y = ((width * sin).powi(2) + (height * cos).powi(2)).sqrt();
+                Rect::new(center.x - x, center.y - y, x * 2.0, y * 2.0)
+            }
+            EditorValues::Rectangle {
+                point,
+                width,
+                height,
+                rotation,
+            } => points(&EditorValues::corners(point, width, height, rotation)),
+            EditorValues::Triangle {
+                point_a,
+                point_b,
+                point_c,
+            } => points(&[point_a, point_b, point_c]),
+            EditorValues::Hexagon {
+                center,
+                radius,
+                vertical,
+            } => points(&EditorValues::vertices(center, radius, vertical)),
+            EditorValues::Polygon { corners, count } => points(&corners[..count]),
+            EditorValues::Marker { point } => Rect::new(point.x, point.y, 0.0, 0.0),
+        }
+    }
+
+    /// Rotates the shape by `angle` radians (clockwise on screen) around `pivot`. Hexagons can
+    /// only be drawn flat or vertical, so they snap to the nearest of the two orientations.
+    pub fn rotate(&mut self, pivot: Vec2, angle: f32) {
+        let rotation = Vec2::from_angle(angle);
+        let turn = |point: &mut Vec2| *point = pivot + rotation.rotate(*point - pivot);
+        match self {
+            EditorValues::Line { point_a, point_b } => {
+                turn(point_a);
+                turn(point_b);
+            }
+            EditorValues::Circle { center, .. } => {
+                turn(center);
+            }
+            EditorValues::Ellipse {
+                center,
+                rotation: ellipse_rotation,
+                ..
+            } => {
+                turn(center);
+                *ellipse_rotation = (*ellipse_rotation + angle.to_degrees()).rem_euclid(360.0);
+            }
+            EditorValues::Rectangle {
+                point,
+                rotation: rectangle_rotation,
+                ..
+            } => {
+                turn(point);
+                *rectangle_rotation =
+                    (*rectangle_rotation + angle).rem_euclid(std::f32::consts::TAU);
+            }
+            EditorValues::Triangle {
+                point_a,
+                point_b,
+                point_c,
+            } => {
+                turn(point_a);
+                turn(point_b);
+                turn(point_c);
+            }
+            EditorValues::Hexagon {
+                center, vertical, ..
+            } => {
+                turn(center);
+                let orientation = if *vertical { 30.0 } else { 0.0 };
+                let orientation = (orientation + angle.to_degrees()).rem_euclid(60.0);
+                *vertical = (15.0..45.0).contains(&orientation);
+            }
+            EditorValues::Polygon { corners, count } => {
+                corners[..*count].iter_mut().for_each(turn);
+            }
+            EditorValues::Marker { point } => {
+                turn(point);
+            }
+        }
+    }
+
+    /// Scales the shape by `factor` per axis around `pivot`; a negative factor mirrors it.
+    /// Circles scaled unevenly become ellipses, rotated rectangles and ellipses keep the scaled
+    /// direction of their own axes and hexagons take the mean factor since they cannot stretch.
+    pub fn scale(&mut self, pivot: Vec2, factor: Vec2) {
+        let stretch = |point: Vec2| pivot + (point - pivot) * factor;
+        *self = match *self {
+            EditorValues::Line { point_a, point_b } => EditorValues::Line {
+                point_a: stretch(point_a),
+                point_b: stretch(point_b),
+            },
+            EditorValues::Circle { center, radius } => {
+                let center = stretch(center);
+                let x = factor.x.abs();
+                let y = factor.y.abs();
+                if x == y {
+                    let radius = radius * x;
+                    EditorValues::Circle { center, radius }
+                } else {
+                    EditorValues::Ellipse {
+                        center,
+                        width: radius * x,
+                        height: radius * y,
+                        rotation: 0.0,
+                    }
+                }
+            }
+            EditorValues::Ellipse {
+                center,
+                width,
+                height,
+                rotation,
+            } => {
+                let axis = Vec2::from_angle(rotation.to_radians());
+                let u = axis * factor;
+                let v = axis.perp() * factor;
+                EditorValues::Ellipse {
+                    center: stretch(center),
+                    width: width * u.length(),
+                    height: height * v.length(),
+                    rotation: u.to_angle().to_degrees().rem_euclid(360.0),
+                }
+            }
+            EditorValues::Rectangle {
+                point,
+                width,
+                height,
+                rotation,
+            } => {
+                let axis = Vec2::from_angle(rotation);
+                let u = axis * factor;
+                let v = axis.perp() * factor;
+                let point = stretch(point);
+                // A mirrored rectangle is anchored at what used to be its bottom-left corner.
+                let point = if u.perp_dot(v) < 0.0 {
+                    point + v * height
+                } else {
+                    point
+                };
+                EditorValues::Rectangle {
+                    point,
+                    width: width * u.length(),
+                    height: height * v.length(),
+                    rotation: u.to_angle().rem_euclid(std::f32::consts::TAU),
+                }
+            }
+            EditorValues::Triangle {
+                point_a,
+                point_b,
+                point_c,
+            } => EditorValues::Triangle {
+                point_a: stretch(point_a),
+                point_b: stretch(point_b),
+                point_c: stretch(point_c),
+            },
+            EditorValues::Hexagon {
+                center,
+                radius,
+                vertical,
+            } => EditorValues::Hexagon {
+                center: stretch(center),
+                radius: radius * (factor.x * factor.y).abs().sqrt(),
+                vertical,
+            },
+            EditorValues::Polygon { corners, count } => EditorValues::Polygon {
+                corners: corners.map(stretch),
+                count,
+            },
+            EditorValues::Marker { point } => EditorValues::Marker {
+                point: stretch(point),
+            },
+        };
+    }
+
+    pub fn translate(&mut self, offset: Vec2) {
+        match self {
+            EditorValues::Line { point_a, point_b } => {
+                *point_a += offset;
+                *point_b += offset;
+            }
+            EditorValues::Circle { center, .. }
+            | EditorValues::Ellipse { center, .. }
+            | EditorValues::Hexagon { center, .. } => {
+                *center += offset;
+            }
+            EditorValues::Rectangle { point, .. } | EditorValues::Marker { point } => {
+                *point += offset;
+            }
+            EditorValues::Triangle {
+                point_a,
+                point_b,
+                point_c,
+            } => {
+                *point_a += offset;
+                *point_b += offset;
+                *point_c += offset;
+            }
+            EditorValues::Polygon { corners, count } => {
+                for corner in corners[..*count].iter_mut() {
+                    *corner += offset;
+                }
+            }
+        }
+    }
+
+    /// Control points edited in vertex mode. Circles and hexagons expose their center and a
+    /// radius handle, ellipses their center and the end of each axis.
+    pub fn points(&self) -> Vec<Vec2> {
+        match *self {
+            EditorValues::Line { point_a, point_b } => vec![point_a, point_b],
+            EditorValues::Circle { center, radius } => {
+                vec![center, center + Vec2::new(radius, 0.0)]
+            }
+            EditorValues::Ellipse {
+                center,
+                width,
+                height,
+                rotation,
+            } => {
+                let axis = Vec2::from_angle(rotation.to_radians());
+                vec![center, center + axis * width, center + axis.perp() * height]
+            }
+            EditorValues::Rectangle {
+                point,
+                width,
+                height,
+                rotation,
+            } => EditorValues::corners(point, width, height, rotation).to_vec(),
+            EditorValues::Triangle {
+                point_a,
+                point_b,
+                point_c,
+            } => vec![point_a, point_b, point_c],
+            EditorValues::Hexagon {
+                center,
+                radius,
+                vertical,
+            } => vec![center, EditorValues::vertices(center, radius, vertical)[0]],
+            EditorValues::Polygon { corners, count } => corners[..count].to_vec(),
+            EditorValues::Marker { point } => vec![point],
+        }
+    }
+
+    /// Points along the outline. Circles and ellipses are sampled in `CURVE_SEGMENTS` steps
+    /// pushed out just enough for the edges to touch the curve, so the outline covers it.
+    pub fn outline(&self) -> Vec<Vec2> {
+        let curve = |center: Vec2, width: f32, height: f32, rotation: f32| {
+            let axis = Vec2::from_angle(rotation);
+            let scale = 1.0 / (std::f32::consts::PI / CURVE_SEGMENTS as f32).cos();
+            let (width, height) = (width * scale, height * scale);
+            (0..CURVE_SEGMENTS)
+                .map(|i| {
+                    let angle = std::f32::consts::TAU * i as f32 / CURVE_SEGMENTS as f32;
+                    let (sin, cos) = angle.sin_cos();
+                    center + axis * width * cos + axis.perp() * height * sin
+                })
+                .collect()
+        };
+        match *self {
+            EditorValues::Circle { center, radius } => curve(center, radius, radius, 0.0),
+            EditorValues::Ellipse {
+                center,
+                width,
+                height,
+                rotation,
+            } => curve(center, width, height, rotation.to_radians()),
+            EditorValues::Hexagon {
+                center,
+                radius,
+                vertical,
+            } => EditorValues::vertices(center, radius, vertical).to_vec(),
+            _ => self.points(),
+        }
+    }
+
+    /// Moves the control point at `index` of `points` to `position`. A rectangle corner is
+    /// moved against the opposite corner, keeping the rotation.
+    pub fn point(&mut self, index: usize, position: Vec2) {
+        match self {
+            EditorValues::Line { point_a, point_b } => match index {
+                0 => *point_a = position,
+                _ => *point_b = position,
+            },
+            EditorValues::Circle { center, radius } => match index {
+                0 => *center = position,
+                _ => *radius = center.distance(position),
+            },
+            EditorValues::Ellipse {
+                center,
+                width,
+                height,
+                rotation,
+            } => {
+                let axis = Vec2::from_angle(rotation.to_radians());
+                match index {
+                    0 => *center = position,
+                    1 => *width = (position - *center).dot(axis).abs(),
+                    _ => *height = (position - *center).dot(axis.perp()).abs(),
+                }
+            }
+            EditorValues::Rectangle {
+                point,
+                width,
+                height,
+                rotation,
+            } => {
+                let opposite =
+                    EditorValues::corners(*point, *width, *height, *rotation)[(index + 2) % 4];
+                let axis = Vec2::from_angle(*rotation);
+                let x = (position - opposite).dot(axis);
+                let y = (position - opposite).dot(axis.perp());
+                *point = opposite + axis * x.min(0.0) + axis.perp() * y.min(0.0);
+                *width = x.abs();
+                *height = y.abs();
+            }
+            EditorValues::Triangle {
+                point_a,
+                point_b,
+                point_c,
+            } => match index {
+                0 => *point_a = position,
+                1 => *point_b = position,
+                _ => *point_c = position,
+            },
+            EditorValues::Hexagon { center, radius, .. } => match index {
+                0 => *center = position,
+                _ => *radius = center.distance(position),
+            },
+            EditorValues::Polygon { corners, .. } => corners[index] = position,
+            EditorValues::Marker { point } => *point = position,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// How an element is painted: an optional fill, an optional outline and the outline thickness.
+/// Lines only use the stroke.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EditorStyle {
+    pub fill: Option<Color>,
+    pub stroke: Option<Color>,
+    pub thickness: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct EditorElement {
-    pub color: Color,
+    pub style: EditorStyle,
     pub value: EditorValues,
-    // use super::EditorShapes;
-
-    // pub struct EditorElement {
-    //     color: Color,
-    //     shape: EditorShapes,
-    //     element: EditorElements,
-    //     // pub id: usize,
-    //     // pub a: Vec2,
-    //     // pub b: Vec2,
-    //     // // pub c: Option<Vec2>,
-    //     // // pub rotation: Option<Vec2>,
-    //     // pub color: Color,
-    //     // pub shape: EditorElements,
-    //     // pub props: EditorElementProps,
-    //     // pub sides: Option<usize>,
-    // }
+    pub link: Option<EditorLink>,
+    pub group: Option<usize>,
+    pub layer: usize,
+    pub name: Option<usize>,
+    /// Indices into `state.tags`, filled from the front.
+    pub tags: [Option<usize>; TAGS_MAX],
+    /// Collision shape, drawn as an overlay and exported apart from the art.
+    pub hitbox: bool,
 }
 
 impl EditorElement {
-    pub fn new(value: EditorValues, color: Color) -> Self {
-        Self { color, value }
+    pub fn new(value: EditorValues, style: EditorStyle) -> Self {
+        let link = None;
+        let group = None;
+        let layer = 0;
+        let name = None;
+        let tags = [None; TAGS_MAX];
+        let hitbox = false;
+        Self {
+            style,
+            value,
+            link,
+            group,
+            layer,
+            name,
+            tags,
+            hitbox,
+        }
     }
-    pub fn draw(&self, color: Option<Color>) {
-        let color = color.unwrap_or(self.color);
+    pub fn contains(&self, position: Vec2) -> bool {
         match self.value {
-            EditorValues::Line {
+            EditorValues::Line { point_a, point_b } => {
+                let line = point_b - point_a;
+                let t = (position - point_a).dot(line) / line.length_squared().max(f32::EPSILON);
+                let nearest = point_a + line * t.clamp(0.0, 1.0);
+                position.distance(nearest) <= self.style.thickness / 2.0 + SIZE_POINT
+            }
+            EditorValues::Circle { center, radius } => position.distance(center) <= radius,
+            EditorValues::Hexagon { center, radius, .. } => position.distance(center) <= radius,
+            EditorValues::Marker { point } => position.distance(point) <= MARKER_SIZE,
+            EditorValues::Ellipse {
+                center,
+                width,
+                height,
+                rotation,
+            } => {
+                let p = Vec2::from_angle(-rotation.to_radians()).rotate(position - center);
+                (p.x / width).powi(2) + (p.y / height).powi(2) <= 1.0
+            }
+            EditorValues::Rectangle {
+                point,
+                width,
+                height,
+                rotation,
+            } => {
+                let p = Vec2::from_angle(-rotation).rotate(position - point);
+                p.x >= 0.0 && p.x <= width && p.y >= 0.0 && p.y <= height
+            }
+            EditorValues::Triangle {
                 point_a,
                 point_b,
-                thickness,
+                point_c,
             } => {
+                let side = |a: Vec2, b: Vec2| (b - a).perp_dot(position - a);
+                let ab = side(point_a, point_b);
+                let bc = side(point_b, point_c);
+                let ca = side(point_c, point_a);
+                (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+            }
+            EditorValues::Polygon { count, .. } if count < 3 => false,
+            EditorValues::Polygon { corners, count } => {
+                let sides: Vec<f32> = (0..count)
+                    .map(|i| {
+                        let a = corners[i];
+                        let b = corners[(i + 1) % count];
+                        (b - a).perp_dot(position - a)
+                    })
+                    .collect();
+                sides.iter().all(|&i| i >= 0.0) || sides.iter().all(|&i| i <= 0.0)
+            }
+        }
+    }
+    pub fn draw(&self, color: Option<Color>) {
+        let fill = self.style.fill.map(|i| color.unwrap_or(i));
+        let stroke = self.style.stroke.map(|i| color.unwrap_or(i));
+        let thickness = self.style.thickness;
+        match self.value {
+            EditorValues::Line { point_a, point_b } => {
                 let a_x = point_a.x;
                 let a_y = point_a.y;
                 let b_x = point_b.x;
                 let b_y = point_b.y;
-                draw_line(a_x, a_y, b_x, b_y, thickness, color);
+                if let Some(stroke) = stroke {
+                    draw_line(a_x, a_y, b_x, b_y, thickness, stroke);
+                }
             }
             // EditorValues::Poly => {
             //     let radius = current.distance(position);
@@ -106,12 +597,12 @@ impl EditorElement {
             EditorValues::Circle { center, radius } => {
                 let x = center.x;
                 let y = center.y;
-                draw_circle(x, y, radius, color);
-            }
-            EditorValues::CircleLine { center, radius } => {
-                let x = center.x;
-                let y = center.y;
-                draw_circle_lines(x, y, radius, 1.0, color);
+                if let Some(fill) = fill {
+                    draw_circle(x, y, radius, fill);
+                }
+                if let Some(stroke) = stroke {
+                    draw_circle_lines(x, y, radius, thickness, stroke);
+                }
             }
             // EditorElements::Arc => {
             //     let radius = current.distance(position);
@@ -130,17 +621,12 @@ impl EditorElement {
             } => {
                 let x = center.x;
                 let y = center.y;
-                draw_ellipse(x, y, width, height, rotation, color);
-            }
-            EditorValues::EllipseLine {
-                center,
-                width,
-                height,
-                rotation,
-            } => {
-                let x = center.x;
-                let y = center.y;
-                draw_ellipse_lines(x, y, width, height, rotation, 1.0, color);
+                if let Some(fill) = fill {
+                    draw_ellipse(x, y, width, height, rotation, fill);
+                }
+                if let Some(stroke) = stroke {
+                    draw_ellipse_lines(x, y, width, height, rotation, thickness, stroke);
+                }
             }
             EditorValues::Rectangle {
                 point,
@@ -151,24 +637,45 @@ impl EditorElement {
                 let x = point.x;
                 let y = point.y;
                 let offset = Vec2::new(0.0, 0.0);
-                draw_rectangle_ex(
-                    x,
-                    y,
-                    width,
-                    height,
-                    DrawRectangleParams {
-                        color,
-                        rotation,
-                        offset,
-                    },
-                );
+                if let Some(fill) = fill {
+                    draw_rectangle_ex(
+                        x,
+                        y,
+                        width,
+                        height,
+                        DrawRectangleParams {
+                            color: fill,
+                            rotation,
+                            offset,
+                        },
+                    );
+                }
+                if let Some(stroke) = stroke {
+                    draw_rectangle_lines_ex(
+                        x,
+                        y,
+                        width,
+                        height,
+                        thickness,
+                        DrawRectangleParams {
+                            color: stroke,
+                            rotation,
+                            offset,
+                        },
+                    );
+                }
             }
             EditorValues::Triangle {
                 point_a,
                 point_b,
                 point_c,
             } => {
-                draw_triangle(point_a, point_b, point_c, color);
+                if let Some(fill) = fill {
+                    draw_triangle(point_a, point_b, point_c, fill);
+                }
+                if let Some(stroke) = stroke {
+                    draw_triangle_lines(point_a, point_b, point_c, thickness, stroke);
+                }
             }
             EditorValues::Hexagon {
                 center,
@@ -177,8 +684,232 @@ impl EditorElement {
             } => {
                 let x = center.x;
                 let y = center.y;
-                draw_hexagon(x, y, radius, 1.0, vertical, color, color);
+                let border = if stroke.is_some() { thickness } else { 0.0 };
+                let border_color = stroke.unwrap_or(BLANK);
+                let fill_color = fill.unwrap_or(BLANK);
+                draw_hexagon(x, y, radius, border, vertical, border_color, fill_color);
+            }
+            EditorValues::Polygon { corners, count } => {
+                if let Some(fill) = fill {
+                    for i in 2..count {
+                        draw_triangle(corners[0], corners[i - 1], corners[i], fill);
+                    }
+                }
+                if let Some(stroke) = stroke {
+                    for i in 0..count {
+                        let a = corners[i];
+                        let b = corners[(i + 1) % count];
+                        draw_line(a.x, a.y, b.x, b.y, thickness, stroke);
+                    }
+                }
+            }
+            EditorValues::Marker { point } => {
+                let color = stroke.or(fill).unwrap_or(WHITE);
+                let (x, y) = (point.x, point.y);
+                draw_circle_lines(x, y, MARKER_SIZE, 1.0, color);
+                draw_line(x - MARKER_SIZE, y, x + MARKER_SIZE, y, 1.0, color);
+                draw_line(x, y - MARKER_SIZE, x, y + MARKER_SIZE, 1.0, color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+
+    use super::EditorElement;
+    use super::EditorStyle;
+    use super::EditorValues;
+    use super::POLYGON_MAX;
+
+    fn near(a: Vec2, b: Vec2) -> bool {
+        a.distance(b) < 1e-3
+    }
+
+    #[test]
+    fn rotate() {
+        let pivot = Vec2::new(10.0, 10.0);
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let mut rectangle = EditorValues::Rectangle {
+            point: Vec2::new(10.0, 10.0),
+            width: 20.0,
+            height: 10.0,
+            rotation: 0.0,
+        };
+        rectangle.rotate(pivot, quarter);
+        let bounds = rectangle.bounds();
+        assert!(near(bounds.point(), Vec2::new(0.0, 10.0)));
+        assert!(near(bounds.size(), Vec2::new(10.0, 20.0)));
+        for _ in 0..3 {
+            rectangle.rotate(pivot, quarter);
+        }
+        assert!(near(rectangle.points()[2], Vec2::new(30.0, 20.0)));
+
+        let mut ellipse = EditorValues::Ellipse {
+            center: Vec2::new(20.0, 10.0),
+            width: 5.0,
+            height: 2.0,
+            rotation: 350.0,
+        };
+        ellipse.rotate(pivot, quarter);
+        let EditorValues::Ellipse {
+            center, rotation, ..
+        } = ellipse
+        else {
+            unreachable!();
+        };
+        assert!(near(center, Vec2::new(10.0, 20.0)));
+        assert!((rotation - 80.0).abs() < 1e-3);
+
+        let mut hexagon = EditorValues::Hexagon {
+            center: pivot,
+            radius: 5.0,
+            vertical: false,
+        };
+        hexagon.rotate(pivot, 20.0_f32.to_radians());
+        assert!(matches!(
+            hexagon,
+            EditorValues::Hexagon { vertical: true, .. }
+        ));
+        hexagon.rotate(pivot, 20.0_f32.to_radians());
+        assert!(matches!(
+            hexagon,
+            EditorValues::Hexagon {
+                vertical: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn scale() {
+        let mut circle = EditorValues::Circle {
+            center: Vec2::new(10.0, 10.0),
+            radius: 5.0,
+        };
+        circle.scale(Vec2::ZERO, Vec2::new(2.0, 2.0));
+        assert_eq!(
+            circle,
+            EditorValues::Circle {
+                center: Vec2::new(20.0, 20.0),
+                radius: 10.0
             }
+        );
+        circle.scale(Vec2::ZERO, Vec2::new(1.0, 0.5));
+        assert!(matches!(
+            circle,
+            EditorValues::Ellipse { width, height, .. } if width == 10.0 && height == 5.0
+        ));
+
+        // Mirroring keeps the size positive and the rectangle in the mirrored bounds.
+        let mut rectangle = EditorValues::Rectangle {
+            point: Vec2::new(10.0, 0.0),
+            width: 20.0,
+            height: 10.0,
+            rotation: 0.0,
+        };
+        rectangle.scale(Vec2::ZERO, Vec2::new(-1.0, 1.0));
+        let bounds = rectangle.bounds();
+        assert!(near(bounds.point(), Vec2::new(-30.0, 0.0)));
+        assert!(near(bounds.size(), Vec2::new(20.0, 10.0)));
+        rectangle.scale(Vec2::ZERO, Vec2::new(1.0, -1.0));
+        let bounds = rectangle.bounds();
+        assert!(near(bounds.point(), Vec2::new(-30.0, -10.0)));
+        assert!(near(bounds.size(), Vec2::new(20.0, 10.0)));
+
+        let mut hexagon = EditorValues::Hexagon {
+            center: Vec2::ZERO,
+            radius: 10.0,
+            vertical: true,
+        };
+        hexagon.scale(Vec2::ZERO, Vec2::new(4.0, 1.0));
+        assert!(matches!(hexagon, EditorValues::Hexagon { radius, .. } if radius == 20.0));
+    }
+
+    #[test]
+    fn polygon_keeps_the_convex_hull() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(5.0, 5.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+        ];
+        let mut value = EditorValues::polygon(&points);
+        assert_eq!(value.points().len(), 4);
+        assert!(!value.points().contains(&Vec2::new(5.0, 5.0)));
+        assert_eq!(value.bounds().size(), Vec2::new(10.0, 10.0));
+
+        value.scale(Vec2::ZERO, Vec2::new(-2.0, 1.0));
+        assert_eq!(value.bounds().point(), Vec2::new(-20.0, 0.0));
+        value.translate(Vec2::new(20.0, 0.0));
+        assert_eq!(value.bounds().point(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn moved_polygon_corners_are_hulled() {
+        let square = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+        ];
+        let mut value = EditorValues::polygon(&square);
+        let index = value
+            .points()
+            .iter()
+            .position(|&i| i == Vec2::ZERO)
+            .unwrap();
+        value.point(index, Vec2::new(6.0, 6.0));
+        value.convex();
+        assert_eq!(value.points().len(), 3);
+        assert!(!value.points().contains(&Vec2::new(6.0, 6.0)));
+
+        let style = EditorStyle {
+            fill: None,
+            stroke: None,
+            thickness: 1.0,
+        };
+        let element = EditorElement::new(value, style);
+        assert!(element.contains(Vec2::new(8.0, 8.0)));
+        assert!(!element.contains(Vec2::new(2.0, 2.0)));
+
+        let line = EditorValues::polygon(&square[..2]);
+        assert!(!EditorElement::new(line, style).contains(Vec2::new(5.0, 0.0)));
+    }
+
+    #[test]
+    fn polygon_covers_more_points_than_it_can_hold() {
+        let points: Vec<Vec2> = (0..32)
+            .map(|i| Vec2::from_angle(std::f32::consts::TAU * i as f32 / 32.0) * 100.0)
+            .collect();
+        let value = EditorValues::polygon(&points);
+        let corners = value.points();
+        assert_eq!(corners.len(), POLYGON_MAX);
+        for point in points {
+            let inside = (0..corners.len()).all(|i| {
+                let a = corners[i];
+                let b = corners[(i + 1) % corners.len()];
+                (b - a).perp_dot(point - a) >= -1e-2
+            });
+            assert!(inside, "{} is outside", point);
+        }
+    }
+
+    #[test]
+    fn only_closed_shapes_are_hitboxes() {
+        let point = Vec2::ZERO;
+        assert!(!EditorValues::Marker { point }.closed());
+        assert!(!EditorValues::Line {
+            point_a: point,
+            point_b: point
+        }
+        .closed());
+        assert!(EditorValues::Circle {
+            center: point,
+            radius: 1.0
         }
+        .closed());
     }
 }
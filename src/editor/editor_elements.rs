@@ -1,43 +1,72 @@
 use macroquad::prelude::draw_circle_lines;
 use macroquad::prelude::draw_line;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::is_key_down;
 use macroquad::prelude::is_mouse_button_pressed;
 use macroquad::prelude::is_mouse_button_released;
+use macroquad::prelude::mouse_wheel;
 use macroquad::prelude::screen_height;
 use macroquad::prelude::screen_width;
+use macroquad::prelude::KeyCode;
 use macroquad::prelude::MouseButton;
 use macroquad::prelude::Vec2;
 use macroquad::prelude::DARKGRAY;
+use macroquad::prelude::GRAY;
 use macroquad::prelude::YELLOW;
 
 // use crate::studio::EditorShapes;
 const SIZE_RESTRICTION: f32 = 10.0;
 
+use super::EditorButton;
 use super::EditorButtons;
 use super::EditorElement;
+use super::EditorGroup;
+use super::EditorHull;
+use super::EditorInspector;
+use super::EditorLayer;
+use super::EditorMetrics;
+use super::EditorPicker;
 use super::EditorState;
+use super::EditorStyle;
+use super::EditorSymmetry;
+use super::EditorTag;
+use super::EditorTransform;
 use super::EditorValues;
+use super::EditorVertex;
+use super::HITBOX_COLOR;
+use super::MARKER_SIZE;
 use super::SIZE_POINT;
+use super::STICKY;
+use super::THICKNESS_STEP;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EditorElements {
     // Arc,
     Line,
     // Poly,
-    // PolyLine,
     Circle,
-    CircleLine,
     Ellipse,
-    EllipseLine,
     Rectangle,
-    // RectangleLine,
     Triangle,
     Hexagon,
+    Polygon,
+    Marker,
 }
 
 impl EditorElements {
     fn element(state: &mut EditorState, current: Vec2, position: Vec2) -> EditorElement {
         let element = state.element;
-        let element_color = state.element_color;
+        let element_style = match element {
+            EditorElements::Line | EditorElements::Marker => {
+                let style = state.style();
+                EditorStyle {
+                    fill: None,
+                    stroke: style.stroke.or(style.fill),
+                    ..style
+                }
+            }
+            _ => state.style(),
+        };
         let element_value = match element {
             EditorElements::Hexagon => {
                 let radius = current.distance(position);
@@ -54,11 +83,6 @@ impl EditorElements {
                 let center = current;
                 EditorValues::Circle { center, radius }
             }
-            EditorElements::CircleLine => {
-                let radius = current.distance(position);
-                let center = current;
-                EditorValues::CircleLine { center, radius }
-            }
             EditorElements::Ellipse => {
                 let width = (position.x - current.x).abs();
                 let height = (position.y - current.y).abs();
@@ -71,27 +95,10 @@ impl EditorElements {
                     rotation,
                 }
             }
-            EditorElements::EllipseLine => {
-                let width = (position.x - current.x).abs();
-                let height = (position.y - current.y).abs();
-                let rotation = 0.0;
-                let center = current;
-                EditorValues::EllipseLine {
-                    center,
-                    height,
-                    width,
-                    rotation,
-                }
-            }
             EditorElements::Line => {
                 let point_a = current;
                 let point_b = position;
-                let thickness = state.element_thickness;
-                EditorValues::Line {
-                    point_a,
-                    point_b,
-                    thickness,
-                }
+                EditorValues::Line { point_a, point_b }
             }
             EditorElements::Rectangle => {
                 let x = current.x.min(position.x);
@@ -107,6 +114,11 @@ impl EditorElements {
                     rotation,
                 }
             }
+            EditorElements::Polygon => {
+                let points = [state.polygon.as_slice(), &[position]].concat();
+                EditorValues::polygon(&points)
+            }
+            EditorElements::Marker => EditorValues::Marker { point: position },
             EditorElements::Triangle => {
                 let point_a = current;
                 let point_b = position;
@@ -118,7 +130,44 @@ impl EditorElements {
                 }
             }
         };
-        EditorElement::new(element_value, element_color)
+        let mut element = EditorElement::new(element_value, element_style);
+        element.layer = state.layer;
+        element.hitbox = state.hitbox && element.value.closed();
+        element
+    }
+
+    /// Adds the polygon drawn so far, with its symmetric copies, as one undo step. Points that
+    /// do not span an area are dropped.
+    fn close(state: &mut EditorState) {
+        if EditorHull::hull(&state.polygon).is_empty() {
+            state.polygon.clear();
+            return;
+        }
+        let first = state.polygon[0];
+        let element = EditorElements::element(state, first, first);
+        state.polygon.clear();
+        let elements = EditorSymmetry::copies(state, element);
+        state.save();
+        state.stack.extend(elements);
+    }
+
+    /// Topmost element under `position` that can be selected in the entered group and layers.
+    pub fn find(state: &EditorState, position: Vec2) -> Option<usize> {
+        EditorLayer::order(state).into_iter().rev().find(|&i| {
+            EditorLayer::editable(state, i)
+                && EditorGroup::editable(state, i)
+                && state.stack[i].contains(position)
+        })
+    }
+
+    pub fn canvas(state: &EditorState) -> bool {
+        EditorButton::find().is_none()
+            && !EditorPicker::hover(state)
+            && EditorTransform::find(state).is_none()
+            && !EditorVertex::hover(state)
+            && !EditorInspector::hover(state)
+            && !EditorLayer::hover(state)
+            && !EditorMetrics::hover(state)
     }
 
     pub fn draw(state: &mut EditorState) {
@@ -127,61 +176,59 @@ impl EditorElements {
         let position = state.position();
 
         if state.draw && !state.drag {
-            if let Some(current) = state.current {
+            // A polygon is previewed from its first point until it is closed.
+            let current = match state.element {
+                EditorElements::Polygon => state.polygon.first().copied(),
+                _ => state.current,
+            };
+            if let Some(current) = current {
                 let element = EditorElements::element(state, current, position);
                 let element_color = DARKGRAY;
-                element.draw(Some(element_color));
+                for element in EditorSymmetry::copies(state, element) {
+                    element.draw(Some(element_color));
+                }
+            }
+            if let (EditorElements::Polygon, Some(first)) = (state.element, state.polygon.first()) {
+                draw_circle_lines(first.x, first.y, STICKY, 1.0, DARKGRAY);
             }
         }
         if !state.draw && state.drag {
-            if let Some(element) = state.stack.iter_mut().find(|i| match i.value {
-                EditorValues::Circle { center, radius } => {
-                    return position.distance(center) <= radius;
-                }
-                EditorValues::Hexagon { center, radius, .. } => {
-                    return position.distance(center) <= radius;
+            if let Some(drag_position) = state.drag_position {
+                let offset = position - drag_position;
+                for &i in state.selection.iter() {
+                    state.stack[i].value.translate(offset);
                 }
-                EditorValues::Rectangle {
-                    point,
-                    width,
-                    height,
-                    rotation,
-                } => {
-                    let p = position - point;
-                    let rotation_sin = rotation.sin();
-                    let rotation_cos = rotation.cos();
-
-                    let x = p.x * rotation_cos + p.y * rotation_sin;
-                    let y = -p.x * rotation_sin + p.y * rotation_cos;
+                state.drag_position = Some(position);
+            }
+        }
 
-                    return x >= -width / 2.0
-                        && x <= width / 2.0
-                        && y >= -height / 2.0
-                        && y <= height / 2.0;
-                }
-                _ => false,
-            }) {
-                match element.value {
-                    EditorValues::Circle { ref mut center, .. } => {
-                        *center = position - state.drag_offset.unwrap_or(position);
-                    }
-                    EditorValues::Hexagon { ref mut center, .. } => {
-                        *center = position - state.drag_offset.unwrap_or(position);
-                    }
-                    EditorValues::Rectangle { ref mut point, .. } => {
-                        *point = position - state.drag_offset.unwrap_or(position);
-                    }
-                    _ => {}
+        for i in EditorLayer::order(state) {
+            if EditorLayer::visible(state, i) {
+                let element = state.stack[i];
+                element.draw(element.hitbox.then_some(HITBOX_COLOR));
+            }
+        }
+        for (i, element) in state.stack.iter().enumerate() {
+            if let (EditorValues::Marker { point }, Some(name)) =
+                (element.value, EditorTag::name(state, element))
+            {
+                if EditorLayer::visible(state, i) {
+                    let x = point.x + MARKER_SIZE * 1.5;
+                    draw_text(name, x, point.y - MARKER_SIZE, 14.0, GRAY);
                 }
             }
         }
 
-        for element in state.stack.iter() {
-            element.draw(None);
+        let selection_color = YELLOW.with_alpha(0.3);
+        for &i in state.selection.iter() {
+            state.stack[i].draw(Some(selection_color));
         }
 
         let color = YELLOW.with_alpha(0.2);
-        for element in state.stack.iter() {
+        for (i, element) in state.stack.iter().enumerate() {
+            if !EditorLayer::visible(state, i) {
+                continue;
+            }
             match element.value {
                 EditorValues::Line {
                     point_a, point_b, ..
@@ -243,27 +290,13 @@ impl EditorElements {
                     }
                 }
                 EditorValues::Rectangle {
-                    width,
-                    height,
-                    rotation,
                     point,
+                    width: rectangle_width,
+                    height: rectangle_height,
+                    rotation,
                 } => {
-                    let hw = width / 2.0;
-                    let hh = height / 2.0;
-
-                    let mut corners = [
-                        Vec2::new(-hw, -hh),
-                        Vec2::new(hw, -hh),
-                        Vec2::new(hw, hh),
-                        Vec2::new(-hw, hh),
-                    ];
-
-                    for corner in &mut corners {
-                        *corner = Vec2::new(
-                            corner.x * rotation.cos() - corner.y * rotation.sin(),
-                            corner.x * rotation.sin() + corner.y * rotation.cos(),
-                        ) + point;
-                    }
+                    let corners =
+                        EditorValues::corners(point, rectangle_width, rectangle_height, rotation);
 
                     let mut highlight = false;
                     for corner in &corners {
@@ -304,68 +337,90 @@ impl EditorElements {
     pub fn actions(state: &mut EditorState) {
         let position = state.position();
 
-        if is_mouse_button_pressed(MouseButton::Left) && state.draw {
+        if is_mouse_button_pressed(MouseButton::Left) && state.draw && EditorElements::canvas(state)
+        {
             state.current = Some(position);
         }
 
+        if state.draw && state.current.is_some() {
+            let (_, wheel) = mouse_wheel();
+            if wheel != 0.0 {
+                state.thickness(THICKNESS_STEP * wheel.signum());
+            }
+        }
+
+        if is_mouse_button_released(MouseButton::Left)
+            && state.draw
+            && state.element == EditorElements::Polygon
+        {
+            // Clicking the first point again closes the polygon, any other click adds a point.
+            if state.current.take().is_some() {
+                let first = state.polygon.first().copied();
+                if first.is_some_and(|i| i.distance(position) <= STICKY) {
+                    EditorElements::close(state);
+                } else if state.polygon.last() != Some(&position) {
+                    state.polygon.push(position);
+                }
+            }
+        }
+        if is_mouse_button_pressed(MouseButton::Right)
+            && state.draw
+            && state.element == EditorElements::Polygon
+        {
+            EditorElements::close(state);
+        }
+
         if is_mouse_button_released(MouseButton::Left) && state.draw {
             if let Some(current) = state.current.take() {
-                if current.distance(position) > SIZE_RESTRICTION {
+                // Markers are placed with a click, shapes need a drag.
+                let marker = state.element == EditorElements::Marker;
+                if marker || current.distance(position) > SIZE_RESTRICTION {
                     let element = EditorElements::element(state, current, position);
+                    let elements = EditorSymmetry::copies(state, element);
                     state.save();
-                    state.stack.push(element);
+                    state.stack.extend(elements);
                 }
             }
         }
 
-        if is_mouse_button_pressed(MouseButton::Left) && !state.draw {
-            if let Some(element) = state.stack.iter().find(|i| match i.value {
-                EditorValues::Circle { center, radius } => {
-                    return position.distance(center) <= radius;
-                }
-                EditorValues::Hexagon { center, radius, .. } => {
-                    return position.distance(center) <= radius;
-                }
-                EditorValues::Rectangle {
-                    point,
-                    width,
-                    height,
-                    rotation,
-                } => {
-                    let p = position - point;
-                    let rotation_sin = rotation.sin();
-                    let rotation_cos = rotation.cos();
-
-                    let x = p.x * rotation_cos + p.y * rotation_sin;
-                    let y = -p.x * rotation_sin + p.y * rotation_cos;
-
-                    return x >= -width / 2.0
-                        && x <= width / 2.0
-                        && y >= -height / 2.0
-                        && y <= height / 2.0;
-                }
-                _ => false,
-            }) {
-                match element.value {
-                    EditorValues::Circle { center, .. } => {
-                        state.drag_offset = Some(position - center);
-                        state.drag = true;
+        if is_mouse_button_pressed(MouseButton::Left)
+            && !state.draw
+            && EditorElements::canvas(state)
+        {
+            let shift = is_key_down(KeyCode::LeftShift);
+            match EditorElements::find(state, position) {
+                Some(index) => {
+                    let mut members = EditorGroup::members(state, index);
+                    members.retain(|&i| EditorLayer::editable(state, i));
+                    if shift {
+                        if state.selection.contains(&index) {
+                            state.selection.retain(|i| !members.contains(i));
+                        } else {
+                            state.selection.extend(members);
+                        }
+                    } else if !state.selection.contains(&index) {
+                        state.selection = members;
                     }
-                    EditorValues::Hexagon { center, .. } => {
-                        state.drag_offset = Some(position - center);
+                    if state.selection.contains(&index) {
+                        state.begin();
+                        state.drag_position = Some(position);
                         state.drag = true;
                     }
-                    EditorValues::Rectangle { point, .. } => {
-                        state.drag_offset = Some(position - point);
-                        state.drag = true;
+                }
+                None => {
+                    if !shift {
+                        state.selection.clear();
                     }
-                    _ => {}
                 }
             }
         }
 
         if is_mouse_button_released(MouseButton::Left) && !state.draw {
+            if state.drag {
+                state.commit();
+            }
             state.drag = false;
+            state.drag_position = None;
         }
     }
 }
@@ -377,12 +432,12 @@ impl From<EditorElements> for EditorButtons {
             EditorElements::Line => EditorButtons::Line,
             // EditorElements::Poly => EditorButtons::Poly,
             EditorElements::Circle => EditorButtons::Circle,
-            EditorElements::CircleLine => EditorButtons::CircleLine,
             EditorElements::Ellipse => EditorButtons::Ellipse,
-            EditorElements::EllipseLine => EditorButtons::EllipseLine,
             EditorElements::Rectangle => EditorButtons::Rectangle,
             EditorElements::Triangle => EditorButtons::Triangle,
             EditorElements::Hexagon => EditorButtons::Hexagon,
+            EditorElements::Polygon => EditorButtons::Polygon,
+            EditorElements::Marker => EditorButtons::Marker,
         }
     }
 }
@@ -0,0 +1,560 @@
+use macroquad::prelude::Color;
+use macroquad::prelude::Rect;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::BLANK;
+use macroquad::prelude::YELLOW;
+
+use super::EditorElement;
+use super::EditorGroup;
+use super::EditorLayer;
+use super::EditorMetrics;
+use super::EditorPivot;
+use super::EditorState;
+use super::EditorTag;
+use super::EditorValues;
+
+/// Rust keywords, which cannot name a field, constant or function without a suffix.
+pub const KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+pub struct EditorExport {}
+
+impl EditorExport {
+    /// Turns free text into a snake_case identifier, keywords taking a trailing `_`. Roles,
+    /// names, tags, groups and layers all go through it so that whatever ends up in the
+    /// exported code compiles.
+    pub fn identifier(text: &str) -> Option<String> {
+        let identifier: String = text
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let identifier = identifier.trim_matches('_');
+        match identifier.chars().next() {
+            None => None,
+            Some(c) if c.is_ascii_digit() => Some(format!("_{}", identifier)),
+            Some(_) if KEYWORDS.contains(&identifier) => Some(format!("{}_", identifier)),
+            Some(_) => Some(identifier.to_string()),
+        }
+    }
+
+    /// Colours that belong to a palette role are emitted as `palette.<role>` so the game can
+    /// swap them at draw time; every other colour is baked in as a literal.
+    fn color(state: &EditorState, color: Color) -> String {
+        match state.palette.role(color) {
+            Some(role) => format!("palette.{}", role),
+            None => format!("{:?}", color),
+        }
+    }
+
+    /// Indices of the exported elements in drawing order. Hidden layers usually hold
+    /// construction guides, left out unless asked for.
+    pub fn elements(state: &EditorState) -> Vec<usize> {
+        EditorLayer::order(state)
+            .into_iter()
+            .filter(|&i| state.export_hidden || EditorLayer::visible(state, i))
+            .collect()
+    }
+
+    /// Collider of a hitbox relative to `origin`, lines and markers have none. Rotated
+    /// rectangles, triangles and hexagons become convex polygons, ellipses a sampled outline.
+    fn collider(value: EditorValues, origin: Vec2) -> Option<String> {
+        let point = |point: Vec2| {
+            let point = point - origin;
+            format!("Vec2::new({:.1}, {:.1})", point.x, point.y)
+        };
+        let polygon = |points: &[Vec2]| {
+            let points: Vec<String> = points.iter().map(|&i| point(i)).collect();
+            format!("Collider::Polygon(&[{}])", points.join(", "))
+        };
+        match value {
+            EditorValues::Circle { center, radius } => Some(format!(
+                "Collider::Circle {{ center: {}, radius: {:.1} }}",
+                point(center),
+                radius
+            )),
+            EditorValues::Rectangle {
+                point: corner,
+                width,
+                height,
+                rotation: 0.0,
+            } => Some(format!(
+                "Collider::Rect(Rect::new({:.1}, {:.1}, {:.1}, {:.1}))",
+                corner.x - origin.x,
+                corner.y - origin.y,
+                width,
+                height
+            )),
+            EditorValues::Rectangle { .. }
+            | EditorValues::Triangle { .. }
+            | EditorValues::Hexagon { .. }
+            | EditorValues::Polygon { .. }
+            | EditorValues::Ellipse { .. } => Some(polygon(&value.outline())),
+            EditorValues::Line { .. } | EditorValues::Marker { .. } => None,
+        }
+    }
+
+    /// Constant `name`, or with the first free `_2`, `_3`... suffix when another constant, or
+    /// one of the fixed ones, already uses it.
+    fn unique(taken: &mut Vec<String>, name: String) -> String {
+        let mut unique = name.clone();
+        let mut n = 1;
+        while taken.contains(&unique) {
+            n += 1;
+            unique = format!("{}_{}", name, n);
+        }
+        taken.push(unique.clone());
+        unique
+    }
+
+    pub fn export(state: &EditorState) -> String {
+        let mut content = String::new();
+
+        let (markers, elements): (Vec<&EditorElement>, Vec<&EditorElement>) =
+            EditorExport::elements(state)
+                .into_iter()
+                .map(|i| &state.stack[i])
+                .partition(|i| matches!(i.value, EditorValues::Marker { .. }));
+        let (hitboxes, elements): (Vec<&EditorElement>, Vec<&EditorElement>) =
+            elements.into_iter().partition(|i| i.hitbox);
+        let bounds = EditorPivot::bounds(state);
+        let origin = EditorPivot::point(state);
+        let origin_x = origin.x;
+        let origin_y = origin.y;
+        let width = bounds.w;
+        let height = bounds.h;
+
+        let mut taken: Vec<String> = ["AREA", "CENTROID", "INERTIA", "HITBOX", "DEFAULT_PALETTE"]
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+
+        // Named parts become constants with their bounds relative to the pivot.
+        let mut names: Vec<(&str, Rect)> = Vec::new();
+        for i in elements.iter() {
+            let Some(name) = EditorTag::name(state, i) else {
+                continue;
+            };
+            let rect = i.value.bounds().offset(-origin);
+            match names.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, named)) => *named = named.combine_with(rect),
+                None => names.push((name, rect)),
+            }
+        }
+        for (name, rect) in names.iter() {
+            content.push_str(&format!(
+                "pub const {}: Rect = Rect::new({:.1}, {:.1}, {:.1}, {:.1});\n",
+                EditorExport::unique(&mut taken, name.to_uppercase()),
+                rect.x,
+                rect.y,
+                rect.w,
+                rect.h
+            ));
+        }
+        if !names.is_empty() {
+            content.push('\n');
+        }
+
+        // Markers are not drawn, they become points relative to the pivot. Unnamed ones are
+        // numbered and a name shared by several markers becomes an array.
+        let mut points: Vec<(Option<&str>, Vec<Vec2>)> = Vec::new();
+        for i in markers.iter() {
+            let EditorValues::Marker { point } = i.value else {
+                continue;
+            };
+            let name = EditorTag::name(state, i);
+            match points
+                .iter_mut()
+                .find(|(n, _)| name.is_some() && *n == name)
+            {
+                Some((_, named)) => named.push(point - origin),
+                None => points.push((name, vec![point - origin])),
+            }
+        }
+        let mut unnamed = 0;
+        let points: Vec<(String, Vec<Vec2>)> = points
+            .into_iter()
+            .map(|(name, named)| {
+                let name = match name {
+                    Some(name) => name.to_uppercase(),
+                    None => {
+                        unnamed += 1;
+                        format!("MARKER_{}", unnamed)
+                    }
+                };
+                (EditorExport::unique(&mut taken, name), named)
+            })
+            .collect();
+        let vec2 = |point: &Vec2| format!("Vec2::new({:.1}, {:.1})", point.x, point.y);
+        for (name, named) in points.iter() {
+            match named.as_slice() {
+                [point] => {
+                    content.push_str(&format!("pub const {}: Vec2 = {};\n", name, vec2(point)));
+                }
+                named => {
+                    let list: Vec<String> = named.iter().map(vec2).collect();
+                    content.push_str(&format!(
+                        "pub const {}: [Vec2; {}] = [{}];\n",
+                        name,
+                        named.len(),
+                        list.join(", ")
+                    ));
+                }
+            }
+        }
+        if !points.is_empty() {
+            content.push('\n');
+        }
+
+        if let Some(metrics) = EditorMetrics::compute(&EditorMetrics::elements(state)) {
+            let centroid = metrics.centroid - origin;
+            content.push_str("// Filled shapes at unit density, overlaps counted once.\n");
+            content.push_str(&format!("pub const AREA: f32 = {:.1};\n", metrics.area));
+            content.push_str(&format!(
+                "pub const CENTROID: Vec2 = Vec2::new({:.1}, {:.1});\n",
+                centroid.x, centroid.y
+            ));
+            content.push_str(&format!(
+                "pub const INERTIA: f32 = {:.1};\n\n",
+                metrics.inertia
+            ));
+        }
+
+        // Hitboxes are left out of the drawing and become colliders relative to the pivot.
+        let colliders: Vec<String> = hitboxes
+            .iter()
+            .filter_map(|i| EditorExport::collider(i.value, origin))
+            .collect();
+        if !colliders.is_empty() {
+            content.push_str("pub enum Collider {\n");
+            content.push_str("   Circle { center: Vec2, radius: f32 },\n");
+            content.push_str("   Rect(Rect),\n");
+            content.push_str("   Polygon(&'static [Vec2]),\n");
+            content.push_str("}\n\n");
+            content.push_str("pub const HITBOX: &[Collider] = &[\n");
+            for collider in colliders {
+                content.push_str(&format!("   {},\n", collider));
+            }
+            content.push_str("];\n\n");
+        }
+
+        let roles = state.palette.roles();
+        if roles.is_empty() {
+            content.push_str("fn draw(x: f32, y: f32) {\n");
+        } else {
+            content.push_str("pub struct ShapePalette {\n");
+            for (role, _) in roles.iter() {
+                content.push_str(&format!("   pub {}: Color,\n", role));
+            }
+            content.push_str("}\n\n");
+            content.push_str("pub const DEFAULT_PALETTE: ShapePalette = ShapePalette {\n");
+            for (role, color) in roles.iter() {
+                content.push_str(&format!(
+                    "   {}: Color::new({:.3}, {:.3}, {:.3}, {:.3}),\n",
+                    role, color.r, color.g, color.b, color.a
+                ));
+            }
+            content.push_str("};\n\n");
+            content.push_str("fn draw(x: f32, y: f32, palette: &ShapePalette) {\n");
+        }
+        content.push_str(&format!(
+            "   draw_rectangle_lines(x + {:.1}, y + {:.1}, {:.1}, {:.1}, 1.2, {:?});\n",
+            bounds.x - origin_x,
+            bounds.y - origin_y,
+            width,
+            height,
+            YELLOW
+        ));
+
+        // Runs of elements from the same group are introduced by a comment naming it.
+        let mut group = None;
+        for i in elements {
+            if i.group != group {
+                group = i.group;
+                let text = match group {
+                    Some(_) => EditorGroup::text(state, group),
+                    None => "ungrouped".to_string(),
+                };
+                content.push_str(&format!("\n   // {}\n", text));
+            }
+            let name = EditorTag::name(state, i).map(str::to_string);
+            let tags = EditorTag::tags(state, i)
+                .into_iter()
+                .map(|i| format!("#{}", i));
+            let labels: Vec<String> = name.into_iter().chain(tags).collect();
+            if !labels.is_empty() {
+                content.push_str(&format!("   // {}\n", labels.join(" ")));
+            }
+            let fill = i.style.fill;
+            let stroke = i.style.stroke;
+            let thickness = i.style.thickness;
+            match i.value {
+                EditorValues::Line { point_a, point_b } => {
+                    if let Some(color) = stroke {
+                        content.push_str(&format!(
+                            "   draw_line(x + {:.1}, y + {:.1}, x + {:.1}, y + {:.1}, {:.1}, {});\n",
+                            point_a.x - origin_x,
+                            point_a.y - origin_y,
+                            point_b.x - origin_x,
+                            point_b.y - origin_y,
+                            thickness,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                }
+                EditorValues::Circle { center, radius } => {
+                    if let Some(color) = fill {
+                        content.push_str(&format!(
+                            "   draw_circle(x + {:.1}, y + {:.1}, {:.1}, {});\n",
+                            center.x - origin_x,
+                            center.y - origin_y,
+                            radius,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                    if let Some(color) = stroke {
+                        content.push_str(&format!(
+                            "   draw_circle_lines(x + {:.1}, y + {:.1}, {:.1}, {:.1}, {});\n",
+                            center.x - origin_x,
+                            center.y - origin_y,
+                            radius,
+                            thickness,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                }
+                EditorValues::Ellipse {
+                    center,
+                    width,
+                    height,
+                    rotation,
+                } => {
+                    if let Some(color) = fill {
+                        content.push_str(&format!(
+                            "   draw_ellipse(x + {:.1}, y + {:.1}, {:.1}, {:.1}, {:.1}, {});\n",
+                            center.x - origin_x,
+                            center.y - origin_y,
+                            width,
+                            height,
+                            rotation,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                    if let Some(color) = stroke {
+                        content.push_str(&format!(
+                            "   draw_ellipse_lines(x + {:.1}, y + {:.1}, {:.1}, {:.1}, {:.1}, {:.1}, {});\n",
+                            center.x - origin_x,
+                            center.y - origin_y,
+                            width,
+                            height,
+                            rotation,
+                            thickness,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                }
+                EditorValues::Rectangle {
+                    width,
+                    height,
+                    rotation,
+                    point,
+                } => {
+                    if let Some(color) = fill {
+                        content.push_str(&format!(
+                            "   draw_rectangle_ex(x + {:.1}, y + {:.1}, {:.1}, {:.1}, DrawRectangleParams {{ offset: Vec2::ZERO, rotation: {:.3}, color: {} }});\n",
+                            point.x - origin_x,
+                            point.y - origin_y,
+                            width,
+                            height,
+                            rotation,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                    if let Some(color) = stroke {
+                        content.push_str(&format!(
+                            "   draw_rectangle_lines_ex(x + {:.1}, y + {:.1}, {:.1}, {:.1}, {:.1}, DrawRectangleParams {{ offset: Vec2::ZERO, rotation: {:.3}, color: {} }});\n",
+                            point.x - origin_x,
+                            point.y - origin_y,
+                            width,
+                            height,
+                            thickness,
+                            rotation,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                }
+                EditorValues::Triangle {
+                    point_a,
+                    point_b,
+                    point_c,
+                } => {
+                    if let Some(color) = fill {
+                        content.push_str(&format!(
+                            "   draw_triangle(Vec2::new(x + {:.1}, y + {:.1}), Vec2::new(x + {:.1}, y + {:.1}), Vec2::new(x + {:.1}, y + {:.1}), {});\n",
+                            point_a.x - origin_x,
+                            point_a.y - origin_y,
+                            point_b.x - origin_x,
+                            point_b.y - origin_y,
+                            point_c.x - origin_x,
+                            point_c.y - origin_y,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                    if let Some(color) = stroke {
+                        content.push_str(&format!(
+                            "   draw_triangle_lines(Vec2::new(x + {:.1}, y + {:.1}), Vec2::new(x + {:.1}, y + {:.1}), Vec2::new(x + {:.1}, y + {:.1}), {:.1}, {});\n",
+                            point_a.x - origin_x,
+                            point_a.y - origin_y,
+                            point_b.x - origin_x,
+                            point_b.y - origin_y,
+                            point_c.x - origin_x,
+                            point_c.y - origin_y,
+                            thickness,
+                            EditorExport::color(state, color),
+                        ));
+                    }
+                }
+                EditorValues::Hexagon {
+                    center,
+                    radius,
+                    vertical,
+                } => {
+                    let border = if stroke.is_some() { thickness } else { 0.0 };
+                    content.push_str(&format!(
+                        "   draw_hexagon(x + {:.1}, y + {:.1}, {:.1}, {:.1}, {:?}, {}, {});\n",
+                        center.x - origin_x,
+                        center.y - origin_y,
+                        radius,
+                        border,
+                        vertical,
+                        EditorExport::color(state, stroke.unwrap_or(BLANK)),
+                        EditorExport::color(state, fill.unwrap_or(BLANK)),
+                    ));
+                }
+                EditorValues::Polygon { corners, count } => {
+                    let point = |i: usize| {
+                        let point = corners[i % count];
+                        (point.x - origin_x, point.y - origin_y)
+                    };
+                    if let Some(color) = fill {
+                        for k in 2..count {
+                            let (a, b, c) = (point(0), point(k - 1), point(k));
+                            content.push_str(&format!(
+                                "   draw_triangle(Vec2::new(x + {:.1}, y + {:.1}), Vec2::new(x + {:.1}, y + {:.1}), Vec2::new(x + {:.1}, y + {:.1}), {});\n",
+                                a.0,
+                                a.1,
+                                b.0,
+                                b.1,
+                                c.0,
+                                c.1,
+                                EditorExport::color(state, color),
+                            ));
+                        }
+                    }
+                    if let Some(color) = stroke {
+                        for k in 0..count {
+                            let (a, b) = (point(k), point(k + 1));
+                            content.push_str(&format!(
+                                "   draw_line(x + {:.1}, y + {:.1}, x + {:.1}, y + {:.1}, {:.1}, {});\n",
+                                a.0,
+                                a.1,
+                                b.0,
+                                b.1,
+                                thickness,
+                                EditorExport::color(state, color),
+                            ));
+                        }
+                    }
+                }
+                EditorValues::Marker { .. } => {}
+            }
+        }
+        content.push_str("}\n");
+        content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+
+    use super::EditorElement;
+    use super::EditorExport;
+    use super::EditorState;
+    use super::EditorTag;
+    use super::EditorValues;
+
+    fn push(state: &mut EditorState, value: EditorValues, name: Option<&str>) {
+        let mut element = EditorElement::new(value, state.style());
+        element.name = name.and_then(|i| EditorTag::parse_name(state, i).unwrap());
+        state.stack.push(element);
+    }
+
+    fn marker(state: &mut EditorState, x: f32, name: Option<&str>) {
+        let point = Vec2::new(x, 0.0);
+        push(state, EditorValues::Marker { point }, name);
+    }
+
+    #[test]
+    fn names_do_not_collide() {
+        let mut state = EditorState::new();
+        let value = EditorValues::Circle {
+            center: Vec2::new(10.0, 10.0),
+            radius: 10.0,
+        };
+        push(&mut state, value, Some("area"));
+        push(&mut state, value, Some("eye"));
+        marker(&mut state, 0.0, Some("eye"));
+        marker(&mut state, 1.0, None);
+        marker(&mut state, 2.0, Some("marker_1"));
+        marker(&mut state, 3.0, Some("spark"));
+        marker(&mut state, 4.0, None);
+        marker(&mut state, 5.0, Some("spark"));
+
+        let content = EditorExport::export(&state);
+        let constants: Vec<&str> = content
+            .lines()
+            .filter_map(|i| i.strip_prefix("pub const "))
+            .filter_map(|i| i.split(':').next())
+            .collect();
+        assert_eq!(
+            constants,
+            vec![
+                "AREA_2",
+                "EYE",
+                "EYE_2",
+                "MARKER_1",
+                "MARKER_1_2",
+                "SPARK",
+                "MARKER_2",
+                "AREA",
+                "CENTROID",
+                "INERTIA",
+            ]
+        );
+        assert!(content.contains("pub const SPARK: [Vec2; 2]"));
+    }
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(
+            EditorExport::identifier(" Dark Skin! "),
+            Some("dark_skin".to_string())
+        );
+        assert_eq!(EditorExport::identifier("2nd"), Some("_2nd".to_string()));
+        assert_eq!(EditorExport::identifier("  "), None);
+        assert_eq!(EditorExport::identifier("Type"), Some("type_".to_string()));
+        assert_eq!(EditorExport::identifier("self"), Some("self_".to_string()));
+    }
+}
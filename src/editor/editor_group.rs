@@ -0,0 +1,260 @@
+use macroquad::prelude::draw_rectangle_lines;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::get_time;
+use macroquad::prelude::is_key_pressed;
+use macroquad::prelude::is_mouse_button_pressed;
+use macroquad::prelude::measure_text;
+use macroquad::prelude::mouse_position;
+use macroquad::prelude::screen_width;
+use macroquad::prelude::KeyCode;
+use macroquad::prelude::MouseButton;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::GREEN;
+
+use super::EditorElements;
+use super::EditorState;
+
+pub const DOUBLE_CLICK: f64 = 0.4;
+
+/// Named group of elements, nested inside `parent`. Elements point at their innermost group
+/// and groups are only ever appended, so undoing the stack never leaves a dangling reference;
+/// regrouping copies the affected groups instead of changing their parent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditorGroup {
+    pub name: String,
+    pub parent: Option<usize>,
+}
+
+impl EditorGroup {
+    /// Groups around `group`, innermost first.
+    pub fn path(state: &EditorState, group: Option<usize>) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut group = group;
+        while let Some(id) = group {
+            path.push(id);
+            group = state.groups[id].parent;
+        }
+        path
+    }
+
+    /// Names of the groups around `group`, outermost first, e.g. `hull / turret`.
+    pub fn text(state: &EditorState, group: Option<usize>) -> String {
+        let path = EditorGroup::path(state, group);
+        let names: Vec<&str> = path
+            .iter()
+            .rev()
+            .map(|&i| state.groups[i].name.as_str())
+            .collect();
+        names.join(" / ")
+    }
+
+    fn used(state: &EditorState, group: usize) -> bool {
+        state
+            .stack
+            .iter()
+            .any(|i| EditorGroup::path(state, i.group).contains(&group))
+    }
+
+    /// Whether element `index` lies inside the entered group.
+    pub fn editable(state: &EditorState, index: usize) -> bool {
+        match state.group {
+            Some(group) => EditorGroup::path(state, state.stack[index].group).contains(&group),
+            None => true,
+        }
+    }
+
+    /// Group picked as a whole when clicking element `index`: the outermost one inside the
+    /// entered group, if any.
+    pub fn unit(state: &EditorState, index: usize) -> Option<usize> {
+        let path = EditorGroup::path(state, state.stack[index].group);
+        let end = state
+            .group
+            .and_then(|group| path.iter().position(|&i| i == group))
+            .unwrap_or(path.len());
+        end.checked_sub(1).map(|i| path[i])
+    }
+
+    /// Element `index` and every element selected along with it.
+    pub fn members(state: &EditorState, index: usize) -> Vec<usize> {
+        match EditorGroup::unit(state, index) {
+            Some(unit) => (0..state.stack.len())
+                .filter(|&i| EditorGroup::path(state, state.stack[i].group).contains(&unit))
+                .collect(),
+            None => vec![index],
+        }
+    }
+
+    /// Group making up the whole selection, if it is exactly one.
+    pub fn selected(state: &EditorState) -> Option<usize> {
+        let mut units = state.selection.iter().map(|&i| EditorGroup::unit(state, i));
+        let unit = units.next()??;
+        units.all(|i| i == Some(unit)).then_some(unit)
+    }
+
+    fn units(state: &EditorState) -> Vec<usize> {
+        let mut units = Vec::new();
+        for &i in state.selection.iter() {
+            if let Some(unit) = EditorGroup::unit(state, i) {
+                if !units.contains(&unit) {
+                    units.push(unit);
+                }
+            }
+        }
+        units
+    }
+
+    /// Creates the groups named by `path`, outermost first, inside the entered group and returns
+    /// the innermost. Paths already created during the same paste are looked up in `created`.
+    pub fn create(
+        state: &mut EditorState,
+        path: &[String],
+        created: &mut Vec<(Vec<String>, usize)>,
+    ) -> Option<usize> {
+        let mut parent = state.group;
+        for k in 1..=path.len() {
+            let prefix = &path[..k];
+            parent = match created.iter().find(|(i, _)| i == prefix) {
+                Some(&(_, id)) => Some(id),
+                None => {
+                    let id = state.groups.len();
+                    let name = path[k - 1].clone();
+                    state.groups.push(EditorGroup { name, parent });
+                    created.push((prefix.to_vec(), id));
+                    Some(id)
+                }
+            };
+        }
+        parent
+    }
+
+    /// Moves `group` with its elements and nested groups under `parent` as a fresh copy.
+    fn reparent(state: &mut EditorState, group: usize, parent: Option<usize>) -> usize {
+        let id = state.groups.len();
+        let name = state.groups[group].name.clone();
+        state.groups.push(EditorGroup { name, parent });
+        let children: Vec<usize> = (0..id)
+            .filter(|&i| state.groups[i].parent == Some(group) && EditorGroup::used(state, i))
+            .collect();
+        for child in children {
+            EditorGroup::reparent(state, child, Some(id));
+        }
+        for element in state.stack.iter_mut() {
+            if element.group == Some(group) {
+                element.group = Some(id);
+            }
+        }
+        id
+    }
+
+    pub fn group(state: &mut EditorState) -> Option<usize> {
+        if state.selection.is_empty() {
+            return None;
+        }
+        state.save();
+        let id = state.groups.len();
+        let name = format!("group_{}", id + 1);
+        let parent = state.group;
+        state.groups.push(EditorGroup { name, parent });
+
+        let units = EditorGroup::units(state);
+        let elements: Vec<usize> = state
+            .selection
+            .iter()
+            .copied()
+            .filter(|&i| EditorGroup::unit(state, i).is_none())
+            .collect();
+        for unit in units {
+            EditorGroup::reparent(state, unit, Some(id));
+        }
+        for i in elements {
+            state.stack[i].group = Some(id);
+        }
+        Some(id)
+    }
+
+    pub fn ungroup(state: &mut EditorState) {
+        let units = EditorGroup::units(state);
+        if units.is_empty() {
+            return;
+        }
+        state.save();
+        for unit in units {
+            let parent = state.groups[unit].parent;
+            let children: Vec<usize> = (0..state.groups.len())
+                .filter(|&i| state.groups[i].parent == Some(unit) && EditorGroup::used(state, i))
+                .collect();
+            for child in children {
+                EditorGroup::reparent(state, child, parent);
+            }
+            for element in state.stack.iter_mut() {
+                if element.group == Some(unit) {
+                    element.group = parent;
+                }
+            }
+        }
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        let Some(group) = state.group else {
+            return;
+        };
+        let bounds = (0..state.stack.len())
+            .filter(|&i| EditorGroup::editable(state, i))
+            .map(|i| state.stack[i].value.bounds())
+            .reduce(|a, b| a.combine_with(b));
+        if let Some(bounds) = bounds {
+            let color = GREEN.with_alpha(0.5);
+            draw_rectangle_lines(
+                bounds.x - 4.0,
+                bounds.y - 4.0,
+                bounds.w + 8.0,
+                bounds.h + 8.0,
+                1.0,
+                color,
+            );
+        }
+        let text = format!(
+            "GROUP {}  [ESC] LEAVE",
+            EditorGroup::text(state, Some(group)).to_uppercase()
+        );
+        let text_dimensions = measure_text(&text, None, 18, 1.0);
+        let x = screen_width() / 2.0 - text_dimensions.width / 2.0;
+        draw_text(&text, x, 40.0, 18.0, GREEN);
+    }
+
+    pub fn actions(state: &mut EditorState) {
+        if state.group.is_some_and(|i| !EditorGroup::used(state, i)) {
+            state.group = None;
+        }
+        if state.input.is_some() {
+            return;
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            if let Some(group) = state.group {
+                state.group = state.groups[group].parent;
+                state.selection.clear();
+            }
+        }
+
+        // Double click enters the group under the cursor.
+        if is_mouse_button_pressed(MouseButton::Left)
+            && !state.draw
+            && EditorElements::canvas(state)
+        {
+            let position: Vec2 = mouse_position().into();
+            let time = get_time();
+            let double = state
+                .click
+                .is_some_and(|(t, p)| time - t < DOUBLE_CLICK && p.distance(position) < 5.0);
+            state.click = if double { None } else { Some((time, position)) };
+            let index = EditorElements::find(state, position);
+            if let Some(unit) = index
+                .filter(|_| double)
+                .and_then(|i| EditorGroup::unit(state, i))
+            {
+                state.group = Some(unit);
+                state.selection.clear();
+            }
+        }
+    }
+}
@@ -44,6 +44,7 @@ impl EditorHelps {
             }
         }
 
+        // The frame used for alignment has the brighter label.
         let display_x = width / 2.0 - DISPLAY_SIZE.x / 2.0;
         let display_y = height / 2.0 - DISPLAY_SIZE.y / 2.0;
         let display_color = RED.with_alpha(0.3);
@@ -57,7 +58,7 @@ impl EditorHelps {
             display_x,
             display_y - 10.0,
             18.0,
-            RED.with_alpha(0.5),
+            RED.with_alpha(if state.display_hd { 0.5 } else { 0.8 }),
         );
         draw_rectangle_lines(
             display_x,
@@ -81,7 +82,7 @@ impl EditorHelps {
             display_x,
             display_y - 10.0,
             18.0,
-            RED.with_alpha(0.5),
+            RED.with_alpha(if state.display_hd { 0.8 } else { 0.5 }),
         );
         draw_rectangle_lines(
             display_x,
@@ -100,25 +101,71 @@ impl EditorHelps {
                 ("[CMD+Y]", "Redo the undone action"),
                 ("[CMD+S]", "Toggle snap mode, align to nearby points"),
                 ("[CMD+G]", "Toggle background grid visibility"),
+                ("[CMD+C]", "Copy the selection"),
+                ("[CMD+V]", "Paste offset, [SHIFT] at the cursor"),
+                ("[CMD+D]", "Duplicate the selection in place"),
+                ("[C]", "Show or hide the color picker"),
+                ("[R]", "Apply the picked color to the selection"),
+                ("[SHIFT+R]", "Replace the selected color everywhere"),
+                ("[WHEEL]", "Change stroke thickness while drawing"),
+                ("[SHIFT]", "Click an element to add it to the selection"),
+                ("[T]", "Rotate the selection by a typed angle"),
+                ("[F]", "Flip the selection horizontally"),
+                ("[SHIFT+F]", "Flip the selection vertically"),
+                ("[ALT+F]", "Flip across the center of the display frames"),
+                ("[ARROW]", "Nudge the selection, [SHIFT] by a grid step"),
+                ("[CMD+ARROW]", "Align the selection to an edge"),
+                ("[A]", "Align centers, [SHIFT+A] vertically"),
+                ("[D]", "Distribute evenly, [SHIFT+D] vertically"),
+                ("[ALT+ALIGN]", "Align to the display frame instead"),
+                ("[W]", "Switch the align frame between SD and HD"),
+                ("]", "Bring forward, [SHIFT] to the front"),
+                ("[", "Send backward, [SHIFT] to the back"),
+                ("[P]", "Repeat the selection along a step"),
+                ("[SHIFT+P]", "Repeat around the cursor by an angle"),
+                ("[G]", "Group the selection, [SHIFT+G] ungroup"),
+                ("[N]", "Rename the selected group"),
+                ("[DBL CLICK]", "Enter a group, [ESC] to leave it"),
+                ("[O]", "Export pivot: top left or center"),
+                ("[SHIFT+O]", "Place the export pivot at the cursor"),
+                ("[M]", "Cycle symmetry: vertical, horizontal, radial"),
+                ("[V]", "Toggle vertex mode, drag points of shapes"),
+                ("[K]", "Toggle hitbox mode, new shapes are hitboxes"),
+                ("[U]", "Hitbox from the convex hull of the drawing"),
+                (
+                    "[SHIFT+U]",
+                    "Hitbox from the bounding circle of the drawing",
+                ),
+                ("[RIGHT CLICK]", "Close a polygon, or click its first point"),
+                ("[L]", "Keep points lying on each other connected"),
+                (
+                    "[SHIFT]",
+                    "Snap rotation to 15 degrees, scale proportionally",
+                ),
+                ("[ALT]", "Scale from the center, detach a connected point"),
                 ("[H]", "Show or hide this help overlay"),
             ];
 
             let text_size = 20.0;
             let spacing = 6.0;
             let line_height = text_size + spacing;
-            let total_height = help_items.len() as f32 * line_height;
+            // Wraps into more columns once the list is taller than the window.
+            let rows = ((screen_height() - 80.0) / line_height).max(1.0) as usize;
+            let total_height = help_items.len().min(rows) as f32 * line_height;
 
             let start_y = screen_height() / 2.0 - total_height / 2.0;
             let padding = 20.0;
+            let column_width = 520.0;
 
             for (i, (shortcut, description)) in help_items.iter().enumerate() {
-                let y = start_y + i as f32 * line_height;
+                let x = padding + (i / rows) as f32 * column_width;
+                let y = start_y + (i % rows) as f32 * line_height;
 
                 if description.is_empty() {
-                    draw_text(shortcut, padding, y, text_size, YELLOW);
+                    draw_text(shortcut, x, y, text_size, YELLOW);
                 } else {
-                    draw_text(shortcut, padding, y, text_size, LIGHTGRAY);
-                    draw_text(description, padding + 80.0, y, text_size, GRAY);
+                    draw_text(shortcut, x, y, text_size, LIGHTGRAY);
+                    draw_text(description, x + 120.0, y, text_size, GRAY);
                 }
             }
         }
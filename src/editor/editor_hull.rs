@@ -0,0 +1,272 @@
+use macroquad::prelude::Vec2;
+
+use super::EditorElement;
+use super::EditorGroup;
+use super::EditorLayer;
+use super::EditorState;
+use super::EditorValues;
+
+/// Collision shapes generated from the drawing, stored as hitboxes so they can be tweaked.
+pub struct EditorHull {}
+
+impl EditorHull {
+    /// Outline points of the visible shapes, markers and hitboxes left out.
+    pub fn points(state: &EditorState) -> Vec<Vec2> {
+        (0..state.stack.len())
+            .filter(|&i| EditorLayer::visible(state, i))
+            .map(|i| state.stack[i])
+            .filter(|i| !i.hitbox && !matches!(i.value, EditorValues::Marker { .. }))
+            .flat_map(|i| i.value.outline())
+            .collect()
+    }
+
+    /// Convex hull in clockwise order on screen (monotone chain), empty when degenerate.
+    pub fn hull(points: &[Vec2]) -> Vec<Vec2> {
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        points.dedup();
+        if points.len() < 3 {
+            return Vec::new();
+        }
+        let mut hull: Vec<Vec2> = Vec::new();
+        for pass in [points.clone(), points.into_iter().rev().collect()] {
+            let start = hull.len();
+            for point in pass {
+                while hull.len() >= start + 2 {
+                    let a = hull[hull.len() - 2];
+                    let b = hull[hull.len() - 1];
+                    if (b - a).perp_dot(point - a) > 0.0 {
+                        break;
+                    }
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            hull.pop();
+        }
+        if hull.len() < 3 {
+            return Vec::new();
+        }
+        hull
+    }
+
+    /// Cuts a convex hull down to `max` corners, each time replacing the edge that adds the least
+    /// area by the point where its neighbouring edges meet, so the result still covers the hull.
+    pub fn reduce(mut hull: Vec<Vec2>, max: usize) -> Vec<Vec2> {
+        while hull.len() > max {
+            let n = hull.len();
+            let best = (0..n)
+                .filter_map(|i| {
+                    let a = hull[i];
+                    let b = hull[(i + 1) % n];
+                    let u = a - hull[(i + n - 1) % n];
+                    let v = b - hull[(i + 2) % n];
+                    let d = u.perp_dot(v);
+                    if d.abs() < f32::EPSILON {
+                        return None;
+                    }
+                    // Both edges have to be extended forward, past `a` and past `b`.
+                    let t = (b - a).perp_dot(v) / d;
+                    let s = (b - a).perp_dot(u) / d;
+                    if t <= 0.0 || s <= 0.0 {
+                        return None;
+                    }
+                    let point = a + u * t;
+                    Some((i, point, (a - point).perp_dot(b - point).abs()))
+                })
+                .min_by(|a, b| a.2.total_cmp(&b.2));
+            let Some((i, point, _)) = best else {
+                break;
+            };
+            hull[i] = point;
+            hull.remove((i + 1) % n);
+        }
+        hull
+    }
+
+    /// Smallest circle around the points (incremental Welzl).
+    pub fn circle(points: &[Vec2]) -> Option<(Vec2, f32)> {
+        let inside =
+            |(center, radius): (Vec2, f32), point: Vec2| point.distance(center) <= radius + 1e-3;
+        let two = |a: Vec2, b: Vec2| ((a + b) / 2.0, a.distance(b) / 2.0);
+        let three = |a: Vec2, b: Vec2, c: Vec2| {
+            let d = 2.0 * (b - a).perp_dot(c - a);
+            if d.abs() < f32::EPSILON {
+                // Collinear, the farthest pair spans the circle.
+                return [two(a, b), two(b, c), two(a, c)]
+                    .into_iter()
+                    .max_by(|x, y| x.1.total_cmp(&y.1))
+                    .unwrap_or((a, 0.0));
+            }
+            let (b, c) = (b - a, c - a);
+            let center = Vec2::new(
+                c.y * b.length_squared() - b.y * c.length_squared(),
+                b.x * c.length_squared() - c.x * b.length_squared(),
+            ) / d;
+            (a + center, center.length())
+        };
+        let mut circle = (*points.first()?, 0.0);
+        for i in 0..points.len() {
+            if inside(circle, points[i]) {
+                continue;
+            }
+            circle = (points[i], 0.0);
+            for j in 0..i {
+                if inside(circle, points[j]) {
+                    continue;
+                }
+                circle = two(points[i], points[j]);
+                for k in 0..j {
+                    if !inside(circle, points[k]) {
+                        circle = three(points[i], points[j], points[k]);
+                    }
+                }
+            }
+        }
+        Some(circle)
+    }
+
+    /// Adds the convex hull, as a single polygon, or the bounding circle of the drawing as a
+    /// hitbox in a new group, one undo step, and selects it.
+    pub fn create(state: &mut EditorState, circle: bool) {
+        let points = EditorHull::points(state);
+        let value = if circle {
+            let Some((center, radius)) = EditorHull::circle(&points) else {
+                return;
+            };
+            EditorValues::Circle { center, radius }
+        } else {
+            if EditorHull::hull(&points).is_empty() {
+                return;
+            }
+            EditorValues::polygon(&points)
+        };
+        state.save();
+        let id = state.groups.len();
+        let name = if circle { "bounding_circle" } else { "hull" };
+        let parent = state.group;
+        state.groups.push(EditorGroup {
+            name: name.to_string(),
+            parent,
+        });
+        let mut element = EditorElement::new(value, state.style());
+        element.layer = state.layer;
+        element.group = Some(id);
+        element.hitbox = true;
+        state.stack.push(element);
+        state.draw = false;
+        state.selection = vec![state.stack.len() - 1];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+
+    use super::EditorElement;
+    use super::EditorHull;
+    use super::EditorState;
+    use super::EditorValues;
+    use crate::editor::POLYGON_MAX;
+
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn hull() {
+        let mut points = square();
+        points.push(Vec2::new(5.0, 5.0));
+        points.push(Vec2::new(5.0, 0.0));
+        points.push(Vec2::new(10.0, 10.0));
+        let hull = EditorHull::hull(&points);
+        assert_eq!(hull.len(), 4);
+        assert!(square().iter().all(|i| hull.contains(i)));
+        // Clockwise on screen, y pointing down.
+        assert!((hull[1] - hull[0]).perp_dot(hull[2] - hull[1]) > 0.0);
+
+        let line = [Vec2::ZERO, Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0)];
+        assert!(EditorHull::hull(&line).is_empty());
+        assert!(EditorHull::hull(&square()[..2]).is_empty());
+    }
+
+    #[test]
+    fn reduce_covers_the_hull() {
+        let hull = EditorHull::hull(&[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, -1.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+        ]);
+        assert_eq!(hull.len(), 5);
+        let reduced = EditorHull::reduce(hull.clone(), 4);
+        assert_eq!(reduced.len(), 4);
+        for point in hull {
+            assert!((0..4).all(|i| {
+                let a = reduced[i];
+                let b = reduced[(i + 1) % 4];
+                (b - a).perp_dot(point - a) >= -1e-3
+            }));
+        }
+        assert_eq!(EditorHull::reduce(square(), 4), square());
+    }
+
+    #[test]
+    fn circle() {
+        assert_eq!(EditorHull::circle(&[]), None);
+        assert_eq!(
+            EditorHull::circle(&[Vec2::new(2.0, 3.0)]),
+            Some((Vec2::new(2.0, 3.0), 0.0))
+        );
+        let (center, radius) = EditorHull::circle(&square()).unwrap();
+        assert!(center.distance(Vec2::new(5.0, 5.0)) < 1e-4);
+        assert!((radius - 50.0_f32.sqrt()).abs() < 1e-4);
+
+        // An obtuse triangle is spanned by its longest side.
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(5.0, 1.0),
+        ];
+        let (center, radius) = EditorHull::circle(&points).unwrap();
+        assert!(center.distance(Vec2::new(5.0, 0.0)) < 1e-4);
+        assert!((radius - 5.0).abs() < 1e-4);
+
+        let line = [Vec2::ZERO, Vec2::new(1.0, 0.0), Vec2::new(4.0, 0.0)];
+        assert_eq!(EditorHull::circle(&line), Some((Vec2::new(2.0, 0.0), 2.0)));
+    }
+
+    #[test]
+    fn create_covers_curves() {
+        let mut state = EditorState::new();
+        let center = Vec2::new(100.0, 100.0);
+        let value = EditorValues::Circle {
+            center,
+            radius: 50.0,
+        };
+        state.stack.push(EditorElement::new(value, state.style()));
+
+        EditorHull::create(&mut state, false);
+        assert_eq!(state.stack.len(), 2);
+        assert_eq!(state.selection, vec![1]);
+        let hitbox = state.stack[1];
+        assert!(hitbox.hitbox);
+        assert_eq!(hitbox.value.points().len(), POLYGON_MAX);
+        for i in 0..360 {
+            let point = center + Vec2::from_angle((i as f32).to_radians()) * 49.9;
+            assert!(hitbox.contains(point), "{} is outside", point);
+        }
+
+        EditorHull::create(&mut state, true);
+        let EditorValues::Circle { radius, .. } = state.stack[2].value else {
+            panic!("expected a circle");
+        };
+        assert!(radius >= 50.0);
+    }
+}
@@ -0,0 +1,144 @@
+use macroquad::prelude::draw_text;
+use macroquad::prelude::get_char_pressed;
+use macroquad::prelude::is_key_pressed;
+use macroquad::prelude::measure_text;
+use macroquad::prelude::screen_height;
+use macroquad::prelude::screen_width;
+use macroquad::prelude::KeyCode;
+use macroquad::prelude::GRAY;
+use macroquad::prelude::RED;
+use macroquad::prelude::YELLOW;
+
+use super::EditorExport;
+use super::EditorInspector;
+use super::EditorPalette;
+use super::EditorProperties;
+use super::EditorRepeat;
+use super::EditorRepeats;
+use super::EditorState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorInputs {
+    Hex,
+    Role,
+    Rotation,
+    Property(EditorProperties),
+    Group(usize),
+    Layer(usize),
+    Repeat(EditorRepeats),
+}
+
+impl EditorInputs {
+    pub fn text(&self) -> &str {
+        match self {
+            EditorInputs::Hex => "HEX",
+            EditorInputs::Role => "ROLE",
+            EditorInputs::Rotation => "ROTATE BY DEGREES",
+            EditorInputs::Property(property) => property.text(),
+            EditorInputs::Group(_) => "GROUP NAME",
+            EditorInputs::Layer(_) => "LAYER NAME",
+            EditorInputs::Repeat(repeat) => repeat.text(),
+        }
+    }
+}
+
+/// Single line of text typed by the user; while it is open the other keyboard shortcuts are
+/// ignored.
+#[derive(Debug, Clone)]
+pub struct EditorInput {
+    pub input: EditorInputs,
+    pub text: String,
+    pub error: Option<String>,
+}
+
+impl EditorInput {
+    pub fn new(input: EditorInputs, text: String) -> Self {
+        let error = None;
+        Self { input, text, error }
+    }
+    /// Applies the text, or explains why it was rejected so the input can stay open.
+    fn apply(&self, state: &mut EditorState) -> Result<(), String> {
+        match self.input {
+            EditorInputs::Hex => {
+                let color = EditorPalette::parse(&self.text).ok_or("EXPECTED #RRGGBBAA")?;
+                state.color(color);
+                state.color_recent(color);
+            }
+            EditorInputs::Role => {
+                if let Some(i) = state.palette.index {
+                    state
+                        .palette
+                        .set_role(i, EditorExport::identifier(&self.text));
+                }
+            }
+            EditorInputs::Rotation => {
+                let degrees = self.text.trim().parse::<f32>();
+                let degrees = degrees.map_err(|_| "EXPECTED A NUMBER")?;
+                state.rotate(degrees.to_radians());
+            }
+            EditorInputs::Property(property) => {
+                EditorInspector::apply(state, property, &self.text)?;
+            }
+            EditorInputs::Group(id) => {
+                let name = EditorExport::identifier(&self.text).ok_or("EXPECTED A NAME")?;
+                state.groups[id].name = name;
+            }
+            EditorInputs::Repeat(repeat) => {
+                EditorRepeat::apply(state, repeat, &self.text)?;
+            }
+            EditorInputs::Layer(id) => {
+                let name = EditorExport::identifier(&self.text).ok_or("EXPECTED A NAME")?;
+                if let Some(layer) = state.layers.iter_mut().find(|i| i.id == id) {
+                    layer.name = name;
+                }
+            }
+        }
+        Ok(())
+    }
+    pub fn draw(state: &mut EditorState) {
+        if let Some(input) = &state.input {
+            let text = format!("{}: {}_", input.input.text(), input.text);
+            let text_size = 20.0;
+            let text_dimensions = measure_text(&text, None, text_size as u16, 1.0);
+            let x = screen_width() / 2.0 - text_dimensions.width / 2.0;
+            let y = screen_height() - 50.0;
+            draw_text(&text, x, y, text_size, YELLOW);
+            match &input.error {
+                Some(error) => draw_text(error, x, y + text_size, text_size * 0.8, RED),
+                None => draw_text(
+                    "[ENTER] apply  [ESC] cancel",
+                    x,
+                    y + text_size,
+                    text_size * 0.8,
+                    GRAY,
+                ),
+            };
+        }
+    }
+    pub fn actions(state: &mut EditorState) {
+        let Some(input) = state.input.as_mut() else {
+            while get_char_pressed().is_some() {}
+            return;
+        };
+        while let Some(c) = get_char_pressed() {
+            if !c.is_control() {
+                input.text.push(c);
+                input.error = None;
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            input.text.pop();
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            state.input = None;
+        }
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+            if let Some(mut input) = state.input.take() {
+                if let Err(error) = input.apply(state) {
+                    input.error = Some(error);
+                    state.input = Some(input);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,473 @@
+use macroquad::prelude::draw_rectangle;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::is_mouse_button_pressed;
+use macroquad::prelude::mouse_position;
+use macroquad::prelude::MouseButton;
+use macroquad::prelude::Rect;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::BLACK;
+use macroquad::prelude::GRAY;
+use macroquad::prelude::LIGHTGRAY;
+use macroquad::prelude::YELLOW;
+
+use super::EditorElement;
+use super::EditorGroup;
+use super::EditorInput;
+use super::EditorInputs;
+use super::EditorPalette;
+use super::EditorState;
+use super::EditorTag;
+use super::EditorValues;
+use super::POLYGON_MAX;
+use super::THICKNESS_MAX;
+use super::THICKNESS_MIN;
+
+pub const INSPECTOR_WIDTH: f32 = 200.0;
+pub const INSPECTOR_ROW: f32 = 22.0;
+
+const POINTS_X: [&str; POLYGON_MAX] = ["X1", "X2", "X3", "X4", "X5", "X6", "X7", "X8"];
+const POINTS_Y: [&str; POLYGON_MAX] = ["Y1", "Y2", "Y3", "Y4", "Y5", "Y6", "Y7", "Y8"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorProperties {
+    Name,
+    Tags,
+    X,
+    Y,
+    PointX(usize),
+    PointY(usize),
+    Radius,
+    Width,
+    Height,
+    Rotation,
+    Vertical,
+    Hitbox,
+    Thickness,
+    Fill,
+    Stroke,
+}
+
+impl EditorProperties {
+    pub fn text(&self) -> &str {
+        match self {
+            EditorProperties::Name => "NAME",
+            EditorProperties::Tags => "TAGS",
+            EditorProperties::X => "X",
+            EditorProperties::Y => "Y",
+            EditorProperties::PointX(i) => POINTS_X[*i],
+            EditorProperties::PointY(i) => POINTS_Y[*i],
+            EditorProperties::Radius => "RADIUS",
+            EditorProperties::Width => "WIDTH",
+            EditorProperties::Height => "HEIGHT",
+            EditorProperties::Rotation => "ROTATION",
+            EditorProperties::Vertical => "VERTICAL",
+            EditorProperties::Hitbox => "HITBOX",
+            EditorProperties::Thickness => "THICKNESS",
+            EditorProperties::Fill => "FILL",
+            EditorProperties::Stroke => "STROKE",
+        }
+    }
+
+    /// Every field of an element. `X` and `Y` are the center of circles, ellipses and
+    /// hexagons, the anchor point of rectangles and the top-left of the bounds otherwise.
+    pub fn list(element: &EditorElement) -> Vec<EditorProperties> {
+        let mut list = vec![
+            EditorProperties::Name,
+            EditorProperties::Tags,
+            EditorProperties::X,
+            EditorProperties::Y,
+        ];
+        match element.value {
+            EditorValues::Line { .. }
+            | EditorValues::Triangle { .. }
+            | EditorValues::Polygon { .. } => {
+                for i in 0..element.value.points().len() {
+                    list.push(EditorProperties::PointX(i));
+                    list.push(EditorProperties::PointY(i));
+                }
+            }
+            EditorValues::Circle { .. } => {
+                list.push(EditorProperties::Radius);
+            }
+            EditorValues::Hexagon { .. } => {
+                list.push(EditorProperties::Radius);
+                list.push(EditorProperties::Vertical);
+            }
+            EditorValues::Ellipse { .. } | EditorValues::Rectangle { .. } => {
+                list.push(EditorProperties::Width);
+                list.push(EditorProperties::Height);
+                list.push(EditorProperties::Rotation);
+            }
+            EditorValues::Marker { .. } => {
+                list.push(EditorProperties::Stroke);
+                return list;
+            }
+        }
+        list.push(EditorProperties::Thickness);
+        if element.value.closed() {
+            list.push(EditorProperties::Hitbox);
+            list.push(EditorProperties::Fill);
+        }
+        list.push(EditorProperties::Stroke);
+        list
+    }
+
+    fn position(value: &EditorValues) -> Vec2 {
+        match *value {
+            EditorValues::Circle { center, .. }
+            | EditorValues::Ellipse { center, .. }
+            | EditorValues::Hexagon { center, .. } => center,
+            EditorValues::Rectangle { point, .. } | EditorValues::Marker { point } => point,
+            EditorValues::Line { .. }
+            | EditorValues::Triangle { .. }
+            | EditorValues::Polygon { .. } => value.bounds().point(),
+        }
+    }
+
+    pub fn value(&self, state: &EditorState, element: &EditorElement) -> String {
+        let color = |color: Option<_>| color.map(EditorPalette::hex).unwrap_or("NONE".to_string());
+        let position = EditorProperties::position(&element.value);
+        let points = element.value.points();
+        match (self, element.value) {
+            (EditorProperties::Name, _) => EditorTag::name(state, element)
+                .unwrap_or("NONE")
+                .to_string(),
+            (EditorProperties::Tags, _) => match EditorTag::tags(state, element).as_slice() {
+                [] => "NONE".to_string(),
+                tags => tags.join(", "),
+            },
+            (EditorProperties::X, _) => format!("{:.1}", position.x),
+            (EditorProperties::Y, _) => format!("{:.1}", position.y),
+            (EditorProperties::PointX(i), _) => format!("{:.1}", points[*i].x),
+            (EditorProperties::PointY(i), _) => format!("{:.1}", points[*i].y),
+            (EditorProperties::Radius, EditorValues::Circle { radius, .. })
+            | (EditorProperties::Radius, EditorValues::Hexagon { radius, .. }) => {
+                format!("{:.1}", radius)
+            }
+            (EditorProperties::Width, EditorValues::Ellipse { width, .. })
+            | (EditorProperties::Width, EditorValues::Rectangle { width, .. }) => {
+                format!("{:.1}", width)
+            }
+            (EditorProperties::Height, EditorValues::Ellipse { height, .. })
+            | (EditorProperties::Height, EditorValues::Rectangle { height, .. }) => {
+                format!("{:.1}", height)
+            }
+            (EditorProperties::Rotation, EditorValues::Ellipse { rotation, .. }) => {
+                format!("{:.1}", rotation)
+            }
+            (EditorProperties::Rotation, EditorValues::Rectangle { rotation, .. }) => {
+                format!("{:.1}", rotation.to_degrees())
+            }
+            (EditorProperties::Vertical, EditorValues::Hexagon { vertical, .. }) => {
+                vertical.to_string()
+            }
+            (EditorProperties::Hitbox, _) => element.hitbox.to_string(),
+            (EditorProperties::Thickness, _) => format!("{:.1}", element.style.thickness),
+            (EditorProperties::Fill, _) => color(element.style.fill),
+            (EditorProperties::Stroke, _) => color(element.style.stroke),
+            _ => String::new(),
+        }
+    }
+
+    /// Sets the property from typed text: rotations in degrees, colors as `#rrggbbaa` or `none`.
+    /// The error describes why the text was rejected.
+    pub fn apply(
+        &self,
+        state: &mut EditorState,
+        element: &mut EditorElement,
+        text: &str,
+    ) -> Result<(), String> {
+        let text = text.trim();
+        match self {
+            EditorProperties::Name => {
+                element.name = EditorTag::parse_name(state, text)?;
+                return Ok(());
+            }
+            EditorProperties::Tags => {
+                element.tags = EditorTag::parse_tags(state, text)?;
+                return Ok(());
+            }
+            EditorProperties::Fill | EditorProperties::Stroke => {
+                let color = if text.eq_ignore_ascii_case("none") {
+                    None
+                } else {
+                    let color = EditorPalette::parse(text);
+                    Some(color.ok_or("EXPECTED #RRGGBBAA OR NONE")?)
+                };
+                let mut style = element.style;
+                if *self == EditorProperties::Fill {
+                    style.fill = color;
+                } else {
+                    style.stroke = color;
+                }
+                match element.value {
+                    EditorValues::Line { .. } if style.stroke.is_none() => {
+                        return Err("LINES NEED A STROKE".to_string());
+                    }
+                    EditorValues::Marker { .. } => {}
+                    _ if style.fill.is_none() && style.stroke.is_none() => {
+                        return Err("SHAPES NEED A FILL OR A STROKE".to_string());
+                    }
+                    _ => {}
+                }
+                element.style = style;
+                return Ok(());
+            }
+            EditorProperties::Hitbox => {
+                element.hitbox = text.parse::<bool>().map_err(|_| "EXPECTED TRUE OR FALSE")?;
+                return Ok(());
+            }
+            EditorProperties::Vertical => {
+                let vertical = text.parse::<bool>().map_err(|_| "EXPECTED TRUE OR FALSE")?;
+                if let EditorValues::Hexagon {
+                    vertical: value, ..
+                } = &mut element.value
+                {
+                    *value = vertical;
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+        let number = text
+            .parse::<f32>()
+            .ok()
+            .filter(|i| i.is_finite())
+            .ok_or("EXPECTED A NUMBER")?;
+        let size = matches!(
+            self,
+            EditorProperties::Radius | EditorProperties::Width | EditorProperties::Height
+        );
+        if size && number < 0.0 {
+            return Err("MUST NOT BE NEGATIVE".to_string());
+        }
+        if *self == EditorProperties::Thickness
+            && !(THICKNESS_MIN..=THICKNESS_MAX).contains(&number)
+        {
+            return Err(format!("BETWEEN {} AND {}", THICKNESS_MIN, THICKNESS_MAX));
+        }
+
+        let position = EditorProperties::position(&element.value);
+        match (self, &mut element.value) {
+            (EditorProperties::X, value) => value.translate(Vec2::new(number - position.x, 0.0)),
+            (EditorProperties::Y, value) => value.translate(Vec2::new(0.0, number - position.y)),
+            (EditorProperties::PointX(i), value) => {
+                let point = value.points()[*i];
+                value.point(*i, Vec2::new(number, point.y));
+                value.convex();
+            }
+            (EditorProperties::PointY(i), value) => {
+                let point = value.points()[*i];
+                value.point(*i, Vec2::new(point.x, number));
+                value.convex();
+            }
+            (EditorProperties::Radius, EditorValues::Circle { radius, .. })
+            | (EditorProperties::Radius, EditorValues::Hexagon { radius, .. })
+            | (EditorProperties::Width, EditorValues::Ellipse { width: radius, .. })
+            | (EditorProperties::Width, EditorValues::Rectangle { width: radius, .. })
+            | (EditorProperties::Height, EditorValues::Ellipse { height: radius, .. })
+            | (EditorProperties::Height, EditorValues::Rectangle { height: radius, .. }) => {
+                *radius = number;
+            }
+            (EditorProperties::Rotation, EditorValues::Ellipse { rotation, .. }) => {
+                *rotation = number.rem_euclid(360.0);
+            }
+            (EditorProperties::Rotation, EditorValues::Rectangle { rotation, .. }) => {
+                *rotation = number.to_radians().rem_euclid(std::f32::consts::TAU);
+            }
+            (EditorProperties::Thickness, _) => {
+                element.style.thickness = number;
+            }
+            _ => return Err(format!("NO {}", self.text())),
+        }
+        Ok(())
+    }
+}
+
+/// Properties of the selection, shown on the left while selecting. With several elements only
+/// the properties they all have are listed, and differing values show as mixed.
+pub struct EditorInspector {}
+
+impl EditorInspector {
+    fn properties(state: &EditorState) -> Vec<EditorProperties> {
+        let mut elements = state.selection.iter().map(|&i| &state.stack[i]);
+        let Some(first) = elements.next() else {
+            return Vec::new();
+        };
+        let mut list = EditorProperties::list(first);
+        for element in elements {
+            let properties = EditorProperties::list(element);
+            list.retain(|i| properties.contains(i));
+        }
+        list
+    }
+
+    /// Shared value of the property across the selection, `None` when it differs.
+    pub fn value(state: &EditorState, property: EditorProperties) -> Option<String> {
+        let mut values = state
+            .selection
+            .iter()
+            .map(|&i| property.value(state, &state.stack[i]));
+        let value = values.next()?;
+        values.all(|i| i == value).then_some(value)
+    }
+
+    /// Applies typed text to every selected element as one undo step, or to none of them.
+    pub fn apply(
+        state: &mut EditorState,
+        property: EditorProperties,
+        text: &str,
+    ) -> Result<(), String> {
+        let mut elements = Vec::new();
+        for k in 0..state.selection.len() {
+            let mut element = state.stack[state.selection[k]];
+            property.apply(state, &mut element, text)?;
+            elements.push(element);
+        }
+        state.save();
+        for (&i, element) in state.selection.iter().zip(elements) {
+            state.stack[i] = element;
+        }
+        Ok(())
+    }
+
+    pub fn list(state: &EditorState) -> Vec<(EditorProperties, Rect)> {
+        if state.draw {
+            return Vec::new();
+        }
+        EditorInspector::properties(state)
+            .into_iter()
+            .enumerate()
+            .map(|(i, property)| {
+                let y = 40.0 + (i + 1) as f32 * INSPECTOR_ROW;
+                (property, Rect::new(10.0, y, INSPECTOR_WIDTH, INSPECTOR_ROW))
+            })
+            .collect()
+    }
+
+    pub fn find(state: &EditorState) -> Option<EditorProperties> {
+        let position: Vec2 = mouse_position().into();
+        EditorInspector::list(state)
+            .into_iter()
+            .find(|(_, rect)| rect.contains(position))
+            .map(|(property, _)| property)
+    }
+
+    pub fn hover(state: &EditorState) -> bool {
+        let position: Vec2 = mouse_position().into();
+        let list = EditorInspector::list(state);
+        let Some(bottom) = list.last().map(|(_, rect)| rect.bottom()) else {
+            return false;
+        };
+        Rect::new(0.0, 30.0, INSPECTOR_WIDTH + 20.0, bottom - 20.0).contains(position)
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        let list = EditorInspector::list(state);
+        let Some(bottom) = list.last().map(|(_, rect)| rect.bottom()) else {
+            return;
+        };
+        draw_rectangle(
+            0.0,
+            30.0,
+            INSPECTOR_WIDTH + 20.0,
+            bottom - 20.0,
+            BLACK.with_alpha(0.8),
+        );
+
+        let title = match (EditorGroup::selected(state), state.selection.as_slice()) {
+            (Some(group), _) => state.groups[group].name.to_uppercase(),
+            (None, [i]) => match state.stack[*i].value {
+                EditorValues::Line { .. } => "LINE".to_string(),
+                EditorValues::Circle { .. } => "CIRCLE".to_string(),
+                EditorValues::Ellipse { .. } => "ELLIPSE".to_string(),
+                EditorValues::Rectangle { .. } => "RECTANGLE".to_string(),
+                EditorValues::Triangle { .. } => "TRIANGLE".to_string(),
+                EditorValues::Hexagon { .. } => "HEXAGON".to_string(),
+                EditorValues::Polygon { .. } => "POLYGON".to_string(),
+                EditorValues::Marker { .. } => "MARKER".to_string(),
+            },
+            (None, selection) => format!("{} SELECTED", selection.len()),
+        };
+        draw_text(&title, 10.0, 40.0 + INSPECTOR_ROW - 6.0, 18.0, YELLOW);
+
+        let position: Vec2 = mouse_position().into();
+        for (property, rect) in list {
+            let editing =
+                matches!(&state.input, Some(i) if i.input == EditorInputs::Property(property));
+            let color = if editing {
+                YELLOW
+            } else if rect.contains(position) {
+                LIGHTGRAY
+            } else {
+                GRAY
+            };
+            let value = EditorInspector::value(state, property).unwrap_or("MIXED".to_string());
+            let y = rect.bottom() - 6.0;
+            draw_text(property.text(), rect.x, y, 18.0, color);
+            draw_text(&value, rect.x + 90.0, y, 18.0, color);
+        }
+    }
+
+    pub fn actions(state: &mut EditorState) {
+        if state.input.is_some() || !is_mouse_button_pressed(MouseButton::Left) {
+            return;
+        }
+        match EditorInspector::find(state) {
+            Some(property @ (EditorProperties::Vertical | EditorProperties::Hitbox)) => {
+                let value = EditorInspector::value(state, property);
+                let text = if value.as_deref() == Some("true") {
+                    "false"
+                } else {
+                    "true"
+                };
+                let _ = EditorInspector::apply(state, property, text);
+            }
+            Some(property) => {
+                let text = EditorInspector::value(state, property).unwrap_or_default();
+                state.input = Some(EditorInput::new(EditorInputs::Property(property), text));
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+
+    use super::EditorElement;
+    use super::EditorProperties;
+    use super::EditorState;
+    use super::EditorValues;
+
+    #[test]
+    fn paint_cannot_be_removed_entirely() {
+        let mut state = EditorState::new();
+        let value = EditorValues::Circle {
+            center: Vec2::ZERO,
+            radius: 5.0,
+        };
+        let mut circle = EditorElement::new(value, state.style());
+        assert_eq!(circle.style.stroke, None);
+        let result = EditorProperties::Fill.apply(&mut state, &mut circle, "none");
+        assert_eq!(result, Err("SHAPES NEED A FILL OR A STROKE".to_string()));
+        assert!(circle.style.fill.is_some());
+
+        EditorProperties::Stroke
+            .apply(&mut state, &mut circle, "#ff0000ff")
+            .unwrap();
+        EditorProperties::Fill
+            .apply(&mut state, &mut circle, "NONE")
+            .unwrap();
+        assert_eq!(circle.style.fill, None);
+
+        let value = EditorValues::Line {
+            point_a: Vec2::ZERO,
+            point_b: Vec2::ONE,
+        };
+        let mut line = EditorElement::new(value, circle.style);
+        let result = EditorProperties::Stroke.apply(&mut state, &mut line, "none");
+        assert_eq!(result, Err("LINES NEED A STROKE".to_string()));
+    }
+}
@@ -0,0 +1,340 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Deepest nesting of arrays and objects accepted, so pasted text cannot exhaust the stack.
+pub const JSON_DEPTH: usize = 64;
+
+/// Minimal JSON value used for palette files and other editor documents.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditorJson {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<EditorJson>),
+    Object(Vec<(String, EditorJson)>),
+}
+
+impl EditorJson {
+    pub fn parse(text: &str) -> Result<EditorJson, String> {
+        let mut chars = text.chars().peekable();
+        let value = EditorJson::value(&mut chars, 0)?;
+        EditorJson::space(&mut chars);
+        match chars.next() {
+            Some(c) => Err(format!("Unexpected '{}' after JSON value", c)),
+            None => Ok(value),
+        }
+    }
+    pub fn get(&self, key: &str) -> Option<&EditorJson> {
+        match self {
+            EditorJson::Object(items) => items.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            EditorJson::String(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            EditorJson::Number(value) => Some(*value as f32),
+            _ => None,
+        }
+    }
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            EditorJson::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+    pub fn as_array(&self) -> Option<&[EditorJson]> {
+        match self {
+            EditorJson::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn space(chars: &mut Peekable<Chars>) {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+    }
+    fn expect(chars: &mut Peekable<Chars>, word: &str) -> Result<(), String> {
+        for expected in word.chars() {
+            if chars.next() != Some(expected) {
+                return Err(format!("Expected '{}'", word));
+            }
+        }
+        Ok(())
+    }
+    fn value(chars: &mut Peekable<Chars>, depth: usize) -> Result<EditorJson, String> {
+        EditorJson::space(chars);
+        if depth >= JSON_DEPTH && matches!(chars.peek(), Some('[' | '{')) {
+            return Err(format!("Nested deeper than {} levels", JSON_DEPTH));
+        }
+        match chars.peek() {
+            Some('n') => EditorJson::expect(chars, "null").map(|_| EditorJson::Null),
+            Some('t') => EditorJson::expect(chars, "true").map(|_| EditorJson::Bool(true)),
+            Some('f') => EditorJson::expect(chars, "false").map(|_| EditorJson::Bool(false)),
+            Some('"') => EditorJson::string(chars).map(EditorJson::String),
+            Some('[') => {
+                chars.next();
+                let mut items = Vec::new();
+                EditorJson::space(chars);
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                    return Ok(EditorJson::Array(items));
+                }
+                loop {
+                    items.push(EditorJson::value(chars, depth + 1)?);
+                    EditorJson::space(chars);
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some(']') => return Ok(EditorJson::Array(items)),
+                        _ => return Err("Expected ',' or ']' in array".to_string()),
+                    }
+                }
+            }
+            Some('{') => {
+                chars.next();
+                let mut items = Vec::new();
+                EditorJson::space(chars);
+                if chars.peek() == Some(&'}') {
+                    chars.next();
+                    return Ok(EditorJson::Object(items));
+                }
+                loop {
+                    EditorJson::space(chars);
+                    let key = EditorJson::string(chars)?;
+                    EditorJson::space(chars);
+                    if chars.next() != Some(':') {
+                        return Err(format!("Expected ':' after \"{}\"", key));
+                    }
+                    items.push((key, EditorJson::value(chars, depth + 1)?));
+                    EditorJson::space(chars);
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some('}') => return Ok(EditorJson::Object(items)),
+                        _ => return Err("Expected ',' or '}' in object".to_string()),
+                    }
+                }
+            }
+            Some(c) if *c == '-' || c.is_ascii_digit() => {
+                let mut number = String::new();
+                while chars
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(*c))
+                {
+                    number.extend(chars.next());
+                }
+                number
+                    .parse()
+                    .map(EditorJson::Number)
+                    .map_err(|_| format!("Invalid number {}", number))
+            }
+            Some(c) => Err(format!("Unexpected '{}'", c)),
+            None => Err("Unexpected end of JSON".to_string()),
+        }
+    }
+    fn string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+        if chars.next() != Some('"') {
+            return Err("Expected string".to_string());
+        }
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some('b') => value.push('\u{8}'),
+                    Some('f') => value.push('\u{c}'),
+                    Some('u') => {
+                        let code = EditorJson::code(chars)?;
+                        // Characters outside the BMP come as a surrogate pair.
+                        let code = if (0xd800..0xdc00).contains(&code) {
+                            let mut rest = chars.clone();
+                            match (rest.next(), rest.next()) {
+                                (Some('\\'), Some('u')) => {
+                                    let low = EditorJson::code(&mut rest)?;
+                                    if (0xdc00..0xe000).contains(&low) {
+                                        *chars = rest;
+                                        0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00)
+                                    } else {
+                                        code
+                                    }
+                                }
+                                _ => code,
+                            }
+                        } else {
+                            code
+                        };
+                        value.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                    }
+                    Some(c @ ('"' | '\\' | '/')) => value.push(c),
+                    Some(c) => return Err(format!("Invalid escape \\{}", c)),
+                    None => return Err("Unexpected end of string".to_string()),
+                },
+                Some(c) => value.push(c),
+                None => return Err("Unexpected end of string".to_string()),
+            }
+        }
+    }
+    fn code(chars: &mut Peekable<Chars>) -> Result<u32, String> {
+        let code: String = chars.by_ref().take(4).collect();
+        if code.len() != 4 || !code.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid escape \\u{}", code));
+        }
+        u32::from_str_radix(&code, 16).map_err(|_| format!("Invalid escape \\u{}", code))
+    }
+    /// Writes a quoted string with JSON escapes, control characters as `\u00xx`.
+    fn escape(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+        write!(f, "\"")?;
+        for c in value.chars() {
+            match c {
+                '"' => write!(f, "\\\"")?,
+                '\\' => write!(f, "\\\\")?,
+                '\n' => write!(f, "\\n")?,
+                '\r' => write!(f, "\\r")?,
+                '\t' => write!(f, "\\t")?,
+                '\u{8}' => write!(f, "\\b")?,
+                '\u{c}' => write!(f, "\\f")?,
+                c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+impl fmt::Display for EditorJson {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EditorJson::Null => write!(f, "null"),
+            EditorJson::Bool(value) => write!(f, "{}", value),
+            // JSON has no NaN or infinity.
+            EditorJson::Number(value) if !value.is_finite() => write!(f, "null"),
+            EditorJson::Number(value) => write!(f, "{}", value),
+            EditorJson::String(value) => EditorJson::escape(f, value),
+            EditorJson::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            EditorJson::Object(items) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    EditorJson::escape(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EditorJson;
+    use super::JSON_DEPTH;
+
+    fn string(value: &str) -> EditorJson {
+        EditorJson::String(value.to_string())
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = EditorJson::Object(vec![
+            ("name".to_string(), string("ship \"one\"")),
+            (
+                "escapes".to_string(),
+                string("a\\b/\n\r\t\u{8}\u{c}\u{1}\u{1f}"),
+            ),
+            ("unicode".to_string(), string("é ✓ 🚀")),
+            (
+                "numbers".to_string(),
+                EditorJson::Array(vec![
+                    EditorJson::Number(0.0),
+                    EditorJson::Number(-1.5),
+                    EditorJson::Number(1e-7),
+                    EditorJson::Number(123456789.0),
+                ]),
+            ),
+            (
+                "flags".to_string(),
+                EditorJson::Array(vec![
+                    EditorJson::Bool(true),
+                    EditorJson::Bool(false),
+                    EditorJson::Null,
+                ]),
+            ),
+            (
+                "nested".to_string(),
+                EditorJson::Object(vec![(
+                    "key \"quoted\"".to_string(),
+                    EditorJson::Array(vec![]),
+                )]),
+            ),
+        ]);
+        let text = json.to_string();
+        assert_eq!(EditorJson::parse(&text), Ok(json));
+    }
+
+    #[test]
+    fn control_characters_are_escaped() {
+        assert_eq!(string("\u{1}").to_string(), "\"\\u0001\"");
+        assert_eq!(string("\u{8}\u{c}").to_string(), "\"\\b\\f\"");
+        assert_eq!(EditorJson::Number(f64::NAN).to_string(), "null");
+    }
+
+    #[test]
+    fn escapes() {
+        let parse = |text: &str| EditorJson::parse(text).unwrap();
+        assert_eq!(parse(r#""\b\f\/\"\\""#), string("\u{8}\u{c}/\"\\"));
+        assert_eq!(parse(r#""\u00e9\u2713""#), string("é✓"));
+        assert_eq!(parse(r#""\ud83d\ude80""#), string("🚀"));
+        assert_eq!(parse(r#""\ud83dx""#), string("\u{fffd}x"));
+        assert_eq!(parse(r#""\ude80""#), string("\u{fffd}"));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(EditorJson::parse(r#""\x""#).is_err());
+        assert!(EditorJson::parse(r#""\u12""#).is_err());
+        assert!(EditorJson::parse(r#""\u+123""#).is_err());
+        assert!(EditorJson::parse(r#""open"#).is_err());
+        assert!(EditorJson::parse("[1,]").is_err());
+        assert!(EditorJson::parse("{\"a\" 1}").is_err());
+        assert!(EditorJson::parse("[1] 2").is_err());
+        assert!(EditorJson::parse("").is_err());
+    }
+
+    #[test]
+    fn depth() {
+        let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+        assert!(EditorJson::parse(&nested(JSON_DEPTH)).is_ok());
+        assert!(EditorJson::parse(&nested(JSON_DEPTH + 1)).is_err());
+        assert!(EditorJson::parse(&"[{\"a\":".repeat(100_000)).is_err());
+    }
+
+    #[test]
+    fn whitespace_and_access() {
+        let json = EditorJson::parse(" { \"a\" : [ 1 , 2.5 ] , \"b\" : true } ").unwrap();
+        let a = json.get("a").and_then(EditorJson::as_array).unwrap();
+        assert_eq!(a[1].as_f32(), Some(2.5));
+        assert_eq!(json.get("b").and_then(EditorJson::as_bool), Some(true));
+        assert_eq!(json.get("c"), None);
+    }
+}
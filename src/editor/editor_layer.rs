@@ -0,0 +1,264 @@
+use macroquad::prelude::draw_rectangle;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::is_mouse_button_pressed;
+use macroquad::prelude::mouse_position;
+use macroquad::prelude::screen_height;
+use macroquad::prelude::MouseButton;
+use macroquad::prelude::Rect;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::BLACK;
+use macroquad::prelude::DARKGRAY;
+use macroquad::prelude::GRAY;
+use macroquad::prelude::GREEN;
+use macroquad::prelude::LIGHTGRAY;
+use macroquad::prelude::YELLOW;
+
+use super::EditorInput;
+use super::EditorInputs;
+use super::EditorState;
+
+pub const LAYER_WIDTH: f32 = 260.0;
+pub const LAYER_ROW: f32 = 22.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorLayers {
+    Name(usize),
+    Visible(usize),
+    Lock(usize),
+    Up(usize),
+    Down(usize),
+    Add,
+    Rename,
+    Move,
+    Hidden,
+}
+
+impl EditorLayers {
+    pub fn text(&self) -> &str {
+        match self {
+            EditorLayers::Name(_) => "",
+            EditorLayers::Visible(_) => "SHOW",
+            EditorLayers::Lock(_) => "LOCK",
+            EditorLayers::Up(_) => "^",
+            EditorLayers::Down(_) => "v",
+            EditorLayers::Add => "ADD",
+            EditorLayers::Rename => "RENAME",
+            EditorLayers::Move => "MOVE",
+            EditorLayers::Hidden => "EXPORT HIDDEN",
+        }
+    }
+}
+
+/// Layer elements are drawn on, back to front in the order of `state.layers`. Layers are never
+/// removed and elements refer to them by `id`, so undo keeps pointing at the right layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditorLayer {
+    pub id: usize,
+    pub name: String,
+    pub visible: bool,
+    pub locked: bool,
+}
+
+impl EditorLayer {
+    pub fn new(id: usize) -> Self {
+        let name = format!("layer_{}", id + 1);
+        Self {
+            id,
+            name,
+            visible: true,
+            locked: false,
+        }
+    }
+
+    pub fn get(state: &EditorState, id: usize) -> Option<&EditorLayer> {
+        state.layers.iter().find(|i| i.id == id)
+    }
+
+    /// Stack indices in drawing order: by layer, then by position in the stack.
+    pub fn order(state: &EditorState) -> Vec<usize> {
+        let position = |id: usize| state.layers.iter().position(|i| i.id == id);
+        let mut order: Vec<usize> = (0..state.stack.len()).collect();
+        order.sort_by_key(|&i| position(state.stack[i].layer));
+        order
+    }
+
+    pub fn visible(state: &EditorState, index: usize) -> bool {
+        EditorLayer::get(state, state.stack[index].layer).is_none_or(|i| i.visible)
+    }
+
+    /// Whether element `index` can be selected, dragged or edited.
+    pub fn editable(state: &EditorState, index: usize) -> bool {
+        EditorLayer::get(state, state.stack[index].layer).is_none_or(|i| i.visible && !i.locked)
+    }
+
+    pub fn list(state: &EditorState) -> Vec<(EditorLayers, Rect)> {
+        if !state.layer_panel {
+            return Vec::new();
+        }
+        let x = 10.0;
+        let mut y = screen_height() - 60.0;
+        let mut list = Vec::new();
+        let buttons = [
+            (EditorLayers::Add, 40.0),
+            (EditorLayers::Rename, 70.0),
+            (EditorLayers::Move, 50.0),
+            (EditorLayers::Hidden, 100.0),
+        ];
+        let mut button_x = x;
+        for (button, width) in buttons {
+            list.push((button, Rect::new(button_x, y, width - 4.0, LAYER_ROW)));
+            button_x += width;
+        }
+        for layer in state.layers.iter() {
+            y -= LAYER_ROW;
+            let id = layer.id;
+            list.push((EditorLayers::Name(id), Rect::new(x, y, 116.0, LAYER_ROW)));
+            list.push((
+                EditorLayers::Visible(id),
+                Rect::new(x + 120.0, y, 46.0, LAYER_ROW),
+            ));
+            list.push((
+                EditorLayers::Lock(id),
+                Rect::new(x + 170.0, y, 46.0, LAYER_ROW),
+            ));
+            list.push((
+                EditorLayers::Up(id),
+                Rect::new(x + 220.0, y, 16.0, LAYER_ROW),
+            ));
+            list.push((
+                EditorLayers::Down(id),
+                Rect::new(x + 240.0, y, 16.0, LAYER_ROW),
+            ));
+        }
+        list
+    }
+
+    pub fn find(state: &EditorState) -> Option<EditorLayers> {
+        let position: Vec2 = mouse_position().into();
+        EditorLayer::list(state)
+            .into_iter()
+            .find(|(_, rect)| rect.contains(position))
+            .map(|(layer, _)| layer)
+    }
+
+    fn panel(state: &EditorState) -> Option<Rect> {
+        let list = EditorLayer::list(state);
+        let top = list.iter().map(|(_, i)| i.top()).reduce(f32::min)?;
+        let bottom = list.iter().map(|(_, i)| i.bottom()).fold(0.0, f32::max);
+        Some(Rect::new(
+            0.0,
+            top - 10.0,
+            LAYER_WIDTH + 20.0,
+            bottom - top + 20.0,
+        ))
+    }
+
+    pub fn hover(state: &EditorState) -> bool {
+        let position: Vec2 = mouse_position().into();
+        EditorLayer::panel(state).is_some_and(|i| i.contains(position))
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        let Some(panel) = EditorLayer::panel(state) else {
+            return;
+        };
+        draw_rectangle(panel.x, panel.y, panel.w, panel.h, BLACK.with_alpha(0.8));
+
+        let position: Vec2 = mouse_position().into();
+        for (item, rect) in EditorLayer::list(state) {
+            let is_position = rect.contains(position);
+            let (text, color) = match item {
+                EditorLayers::Name(id) => {
+                    let layer = EditorLayer::get(state, id).unwrap();
+                    let color = if id == state.layer { YELLOW } else { GRAY };
+                    (layer.name.as_str(), color)
+                }
+                EditorLayers::Visible(id) => {
+                    let visible = EditorLayer::get(state, id).is_some_and(|i| i.visible);
+                    let text = if visible { "SHOW" } else { "HIDE" };
+                    (text, if visible { GREEN } else { DARKGRAY })
+                }
+                EditorLayers::Lock(id) => {
+                    let locked = EditorLayer::get(state, id).is_some_and(|i| i.locked);
+                    (item.text(), if locked { YELLOW } else { DARKGRAY })
+                }
+                EditorLayers::Move if state.selection.is_empty() => (item.text(), DARKGRAY),
+                EditorLayers::Hidden if state.export_hidden => (item.text(), GREEN),
+                _ => (item.text(), GRAY),
+            };
+            let color = if is_position { LIGHTGRAY } else { color };
+            draw_text(text, rect.x, rect.bottom() - 6.0, 18.0, color);
+        }
+    }
+
+    pub fn actions(state: &mut EditorState) {
+        if state.input.is_some() || !is_mouse_button_pressed(MouseButton::Left) {
+            return;
+        }
+        let Some(item) = EditorLayer::find(state) else {
+            return;
+        };
+        let position =
+            |state: &EditorState, id: usize| state.layers.iter().position(|i| i.id == id);
+        match item {
+            EditorLayers::Name(id) => {
+                state.layer = id;
+            }
+            EditorLayers::Visible(id) => {
+                if let Some(i) = position(state, id) {
+                    state.layers[i].visible = !state.layers[i].visible;
+                }
+                EditorLayer::deselect(state);
+            }
+            EditorLayers::Lock(id) => {
+                if let Some(i) = position(state, id) {
+                    state.layers[i].locked = !state.layers[i].locked;
+                }
+                EditorLayer::deselect(state);
+            }
+            EditorLayers::Up(id) => {
+                if let Some(i) = position(state, id).filter(|&i| i + 1 < state.layers.len()) {
+                    state.layers.swap(i, i + 1);
+                }
+            }
+            EditorLayers::Down(id) => {
+                if let Some(i) = position(state, id).filter(|&i| i > 0) {
+                    state.layers.swap(i, i - 1);
+                }
+            }
+            EditorLayers::Add => {
+                let id = state.layers.iter().map(|i| i.id + 1).max().unwrap_or(0);
+                let index = position(state, state.layer).map_or(state.layers.len(), |i| i + 1);
+                state.layers.insert(index, EditorLayer::new(id));
+                state.layer = id;
+            }
+            EditorLayers::Rename => {
+                if let Some(layer) = EditorLayer::get(state, state.layer) {
+                    let text = layer.name.clone();
+                    state.input = Some(EditorInput::new(EditorInputs::Layer(state.layer), text));
+                }
+            }
+            EditorLayers::Move => {
+                if !state.selection.is_empty() {
+                    state.save();
+                    for &i in state.selection.iter() {
+                        state.stack[i].layer = state.layer;
+                    }
+                    EditorLayer::deselect(state);
+                }
+            }
+            EditorLayers::Hidden => {
+                state.export_hidden = !state.export_hidden;
+            }
+        }
+    }
+
+    /// Drops elements that can no longer be edited from the selection.
+    fn deselect(state: &mut EditorState) {
+        let selection = std::mem::take(&mut state.selection);
+        state.selection = selection
+            .into_iter()
+            .filter(|&i| EditorLayer::editable(state, i))
+            .collect();
+    }
+}
@@ -0,0 +1,264 @@
+use macroquad::prelude::draw_line;
+use macroquad::prelude::draw_rectangle;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::mouse_position;
+use macroquad::prelude::screen_height;
+use macroquad::prelude::screen_width;
+use macroquad::prelude::Rect;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::BLACK;
+use macroquad::prelude::GRAY;
+use macroquad::prelude::SKYBLUE;
+use macroquad::prelude::YELLOW;
+
+use super::EditorElement;
+use super::EditorExport;
+use super::EditorPivot;
+use super::EditorState;
+use super::EditorValues;
+
+pub const METRICS_WIDTH: f32 = 240.0;
+pub const METRICS_ROW: f32 = 22.0;
+pub const METRICS_SAMPLES: usize = 256;
+
+/// Mass properties of the filled shapes at unit density, sampled on a grid over their bounds so
+/// overlapping shapes count once. The inertia is the polar moment about the centroid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EditorMetrics {
+    pub area: f32,
+    pub centroid: Vec2,
+    pub inertia: f32,
+}
+
+impl EditorMetrics {
+    /// Exported shapes with a fill, markers and hitboxes left out.
+    pub fn elements(state: &EditorState) -> Vec<EditorElement> {
+        EditorExport::elements(state)
+            .into_iter()
+            .map(|i| state.stack[i])
+            .filter(|i| i.style.fill.is_some() && !i.hitbox)
+            .filter(|i| !matches!(i.value, EditorValues::Marker { .. }))
+            .collect()
+    }
+
+    fn inside(element: &EditorElement, position: Vec2) -> bool {
+        match element.value {
+            // Selection treats hexagons as their circumcircle, the area needs the real outline.
+            EditorValues::Hexagon { .. } => {
+                let outline = element.value.outline();
+                (0..outline.len()).all(|i| {
+                    let a = outline[i];
+                    let b = outline[(i + 1) % outline.len()];
+                    (b - a).perp_dot(position - a) >= 0.0
+                })
+            }
+            _ => element.contains(position),
+        }
+    }
+
+    pub fn compute(elements: &[EditorElement]) -> Option<EditorMetrics> {
+        let bounds = elements
+            .iter()
+            .map(|i| i.value.bounds())
+            .reduce(|a, b| a.combine_with(b))?;
+        let step = bounds.w.max(bounds.h) / METRICS_SAMPLES as f32;
+        if step <= 0.0 {
+            return None;
+        }
+        let columns = (bounds.w / step).ceil() as usize;
+        let rows = (bounds.h / step).ceil() as usize;
+        let cell = (step as f64).powi(2);
+        // Sums are taken around the bounds center in f64 to keep the precision of the inertia.
+        let center = bounds.center();
+        let (mut area, mut x, mut y, mut second) = (0.0, 0.0, 0.0, 0.0);
+        for row in 0..rows {
+            for column in 0..columns {
+                let sample = Vec2::new(column as f32 + 0.5, row as f32 + 0.5) * step;
+                let position = bounds.point() + sample;
+                if !elements.iter().any(|i| EditorMetrics::inside(i, position)) {
+                    continue;
+                }
+                let offset = (position - center).as_dvec2();
+                area += cell;
+                x += offset.x * cell;
+                y += offset.y * cell;
+                // Each cell also spins around its own center.
+                second += offset.length_squared() * cell + cell * cell / 6.0;
+            }
+        }
+        if area <= 0.0 {
+            return None;
+        }
+        let (x, y) = (x / area, y / area);
+        Some(EditorMetrics {
+            area: area as f32,
+            centroid: center + Vec2::new(x as f32, y as f32),
+            inertia: (second - area * (x * x + y * y)) as f32,
+        })
+    }
+
+    /// Metrics of the current drawing, computed again only when its filled shapes change.
+    pub fn get(state: &mut EditorState) -> Option<EditorMetrics> {
+        let elements = EditorMetrics::elements(state);
+        if elements != state.metrics_elements {
+            state.metrics = EditorMetrics::compute(&elements);
+            state.metrics_elements = elements;
+        }
+        state.metrics
+    }
+
+    pub fn panel(state: &EditorState) -> Option<Rect> {
+        if !state.metrics_panel {
+            return None;
+        }
+        let h = METRICS_ROW * 4.0 + 10.0;
+        let x = screen_width() - METRICS_WIDTH - 20.0;
+        let y = screen_height() - 40.0 - h;
+        Some(Rect::new(x, y, METRICS_WIDTH + 20.0, h))
+    }
+
+    pub fn hover(state: &EditorState) -> bool {
+        let position: Vec2 = mouse_position().into();
+        EditorMetrics::panel(state).is_some_and(|i| i.contains(position))
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        let Some(panel) = EditorMetrics::panel(state) else {
+            return;
+        };
+        draw_rectangle(panel.x, panel.y, panel.w, panel.h, BLACK.with_alpha(0.8));
+        let x = panel.x + 10.0;
+        let row = |i: usize| panel.y + (i + 1) as f32 * METRICS_ROW - 2.0;
+        draw_text("METRICS", x, row(0), 18.0, YELLOW);
+
+        let Some(metrics) = EditorMetrics::get(state) else {
+            draw_text("NO FILLED SHAPES", x, row(1), 18.0, GRAY);
+            return;
+        };
+        let centroid = metrics.centroid - EditorPivot::point(state);
+        let rows = [
+            ("AREA", format!("{:.1}", metrics.area)),
+            ("CENTROID", format!("{:.1}, {:.1}", centroid.x, centroid.y)),
+            ("INERTIA", format!("{:.1}", metrics.inertia)),
+        ];
+        for (i, (label, value)) in rows.iter().enumerate() {
+            draw_text(label, x, row(i + 1), 18.0, GRAY);
+            draw_text(value, x + 90.0, row(i + 1), 18.0, GRAY);
+        }
+
+        let point = metrics.centroid;
+        let size = 6.0;
+        draw_line(
+            point.x - size,
+            point.y - size,
+            point.x + size,
+            point.y + size,
+            1.0,
+            SKYBLUE,
+        );
+        draw_line(
+            point.x - size,
+            point.y + size,
+            point.x + size,
+            point.y - size,
+            1.0,
+            SKYBLUE,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+
+    use super::EditorElement;
+    use super::EditorMetrics;
+    use super::EditorState;
+    use super::EditorValues;
+
+    fn element(value: EditorValues) -> EditorElement {
+        EditorElement::new(value, EditorState::new().style())
+    }
+
+    fn rectangle(x: f32, y: f32, width: f32, height: f32) -> EditorElement {
+        element(EditorValues::Rectangle {
+            point: Vec2::new(x, y),
+            width,
+            height,
+            rotation: 0.0,
+        })
+    }
+
+    fn close(value: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (value - expected).abs() <= expected.abs() * tolerance,
+            "{} is not close to {}",
+            value,
+            expected
+        );
+    }
+
+    #[test]
+    fn rectangle_metrics() {
+        let metrics = EditorMetrics::compute(&[rectangle(10.0, 20.0, 100.0, 50.0)]).unwrap();
+        close(metrics.area, 5000.0, 1e-3);
+        assert!(metrics.centroid.distance(Vec2::new(60.0, 45.0)) < 0.1);
+        // Polar moment of a rectangle: m (w² + h²) / 12.
+        close(
+            metrics.inertia,
+            5000.0 * (100.0 * 100.0 + 50.0 * 50.0) / 12.0,
+            1e-3,
+        );
+    }
+
+    #[test]
+    fn circle_and_hexagon_metrics() {
+        let center = Vec2::new(50.0, 50.0);
+        let circle = element(EditorValues::Circle {
+            center,
+            radius: 50.0,
+        });
+        let metrics = EditorMetrics::compute(&[circle]).unwrap();
+        let pi = std::f32::consts::PI;
+        close(metrics.area, pi * 2500.0, 1e-2);
+        assert!(metrics.centroid.distance(center) < 0.1);
+        close(metrics.inertia, pi * 50.0_f32.powi(4) / 2.0, 1e-2);
+
+        let hexagon = element(EditorValues::Hexagon {
+            center,
+            radius: 50.0,
+            vertical: false,
+        });
+        let metrics = EditorMetrics::compute(&[hexagon]).unwrap();
+        close(metrics.area, 3.0 * 3.0_f32.sqrt() / 2.0 * 2500.0, 1e-2);
+    }
+
+    #[test]
+    fn overlaps_count_once() {
+        let a = rectangle(0.0, 0.0, 100.0, 100.0);
+        let b = rectangle(50.0, 0.0, 100.0, 100.0);
+        let metrics = EditorMetrics::compute(&[a, b]).unwrap();
+        close(metrics.area, 15000.0, 1e-2);
+        assert!(metrics.centroid.distance(Vec2::new(75.0, 50.0)) < 0.1);
+        assert_eq!(EditorMetrics::compute(&[]), None);
+        assert_eq!(
+            EditorMetrics::compute(&[rectangle(0.0, 0.0, 0.0, 0.0)]),
+            None
+        );
+    }
+
+    #[test]
+    fn elements_leave_out_markers_hitboxes_and_unfilled_shapes() {
+        let mut state = EditorState::new();
+        state.stack.push(rectangle(0.0, 0.0, 10.0, 10.0));
+        let mut hitbox = rectangle(0.0, 0.0, 20.0, 20.0);
+        hitbox.hitbox = true;
+        state.stack.push(hitbox);
+        let mut outline = rectangle(0.0, 0.0, 30.0, 30.0);
+        outline.style.fill = None;
+        state.stack.push(outline);
+        let point = Vec2::ZERO;
+        state.stack.push(element(EditorValues::Marker { point }));
+        assert_eq!(EditorMetrics::elements(&state), vec![state.stack[0]]);
+    }
+}
@@ -0,0 +1,325 @@
+use macroquad::prelude::Color;
+use macroquad::prelude::DARKGRAY;
+use macroquad::prelude::GRAY;
+use macroquad::prelude::GREEN;
+use macroquad::prelude::LIGHTGRAY;
+use macroquad::prelude::ORANGE;
+use macroquad::prelude::PINK;
+use macroquad::prelude::PURPLE;
+use macroquad::prelude::RED;
+use macroquad::prelude::YELLOW;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::EditorExport;
+use super::EditorJson;
+
+pub const COLORS: [Color; 9] = [
+    DARKGRAY.with_alpha(0.5),
+    GRAY.with_alpha(0.5),
+    GREEN.with_alpha(0.5),
+    PINK.with_alpha(0.5),
+    LIGHTGRAY.with_alpha(0.5),
+    ORANGE.with_alpha(0.5),
+    PURPLE.with_alpha(0.5),
+    RED.with_alpha(0.5),
+    YELLOW.with_alpha(0.5),
+];
+
+pub const PALETTE_JSON: &str = "palette.json";
+pub const PALETTE_GPL: &str = "palette.gpl";
+
+/// Palette slot; a slot with a role becomes a field of the exported `ShapePalette`.
+#[derive(Debug, Clone)]
+pub struct EditorSwatch {
+    pub color: Color,
+    pub role: Option<String>,
+}
+
+impl EditorSwatch {
+    pub fn new(color: Color, role: Option<String>) -> Self {
+        Self { color, role }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EditorPalette {
+    pub name: String,
+    pub swatches: Vec<EditorSwatch>,
+    pub index: Option<usize>,
+}
+
+impl EditorPalette {
+    pub fn new() -> Self {
+        let name = "unkNOWn Shape".to_string();
+        let swatches = COLORS.iter().map(|&i| EditorSwatch::new(i, None)).collect();
+        let index = None;
+        Self {
+            name,
+            swatches,
+            index,
+        }
+    }
+
+    /// Role of the first swatch with exactly this colour.
+    pub fn role(&self, color: Color) -> Option<&str> {
+        self.swatches
+            .iter()
+            .find(|i| i.color == color && i.role.is_some())
+            .and_then(|i| i.role.as_deref())
+    }
+
+    /// Roles in palette order with the colour of their swatch.
+    pub fn roles(&self) -> Vec<(&str, Color)> {
+        self.swatches
+            .iter()
+            .filter_map(|i| i.role.as_deref().map(|role| (role, i.color)))
+            .collect()
+    }
+
+    /// A role belongs to a single swatch, so it is taken from any other swatch that had it.
+    pub fn set_role(&mut self, index: usize, role: Option<String>) {
+        if role.is_some() {
+            for swatch in self.swatches.iter_mut().filter(|i| i.role == role) {
+                swatch.role = None;
+            }
+        }
+        self.swatches[index].role = role;
+    }
+
+    /// Keeps the first swatch of each role when a file repeats one.
+    fn unique_roles(swatches: &mut [EditorSwatch]) {
+        for i in 0..swatches.len() {
+            if swatches[..i]
+                .iter()
+                .any(|j| j.role.is_some() && j.role == swatches[i].role)
+            {
+                swatches[i].role = None;
+            }
+        }
+    }
+
+    pub fn hex(color: Color) -> String {
+        let [r, g, b, a]: [u8; 4] = color.into();
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+
+    pub fn parse(hex: &str) -> Option<Color> {
+        let hex = hex.trim().trim_start_matches('#');
+        if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        let a = if hex.len() == 8 { channel(6)? } else { 255 };
+        Some(Color::from_rgba(channel(0)?, channel(2)?, channel(4)?, a))
+    }
+
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        if Path::new(PALETTE_JSON).exists() {
+            EditorPalette::load_json(PALETTE_JSON)
+        } else {
+            EditorPalette::load_gpl(PALETTE_GPL)
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        self.save_json(PALETTE_JSON)?;
+        self.save_gpl(PALETTE_GPL)
+    }
+
+    pub fn load_json(path: &str) -> Result<Self, Box<dyn Error>> {
+        let data =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let json = EditorJson::parse(&data).map_err(|e| format!("{} is invalid: {}", path, e))?;
+        let name = json
+            .get("name")
+            .and_then(EditorJson::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let mut swatches = json
+            .get("colors")
+            .and_then(EditorJson::as_array)
+            .ok_or_else(|| format!("{} has no colors", path))?
+            .iter()
+            .map(|i| {
+                let color = i
+                    .get("color")
+                    .and_then(EditorJson::as_str)
+                    .and_then(EditorPalette::parse)
+                    .ok_or_else(|| format!("{} has an invalid color: {}", path, i))?;
+                let role = i
+                    .get("role")
+                    .and_then(EditorJson::as_str)
+                    .and_then(EditorExport::identifier);
+                Ok(EditorSwatch::new(color, role))
+            })
+            .collect::<Result<Vec<EditorSwatch>, String>>()?;
+        EditorPalette::unique_roles(&mut swatches);
+        let index = None;
+        Ok(Self {
+            name,
+            swatches,
+            index,
+        })
+    }
+
+    pub fn save_json(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let colors = self
+            .swatches
+            .iter()
+            .map(|i| {
+                let mut items = vec![(
+                    "color".to_string(),
+                    EditorJson::String(EditorPalette::hex(i.color)),
+                )];
+                if let Some(role) = &i.role {
+                    items.push(("role".to_string(), EditorJson::String(role.clone())));
+                }
+                EditorJson::Object(items)
+            })
+            .collect();
+        let json = EditorJson::Object(vec![
+            ("name".to_string(), EditorJson::String(self.name.clone())),
+            ("colors".to_string(), EditorJson::Array(colors)),
+        ]);
+        fs::write(path, json.to_string())
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        Ok(())
+    }
+
+    /// GIMP palettes have no alpha channel, so the colour name carries the full `#rrggbbaa`
+    /// value followed by the role; entries with any other name load as opaque.
+    pub fn load_gpl(path: &str) -> Result<Self, Box<dyn Error>> {
+        let data =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let mut lines = data.lines();
+        if lines.next().map(str::trim) != Some("GIMP Palette") {
+            return Err(format!("{} is not a GIMP palette", path).into());
+        }
+        let mut name = String::new();
+        let mut swatches = Vec::new();
+        for line in lines {
+            let line = line.trim();
+            if let Some(value) = line.strip_prefix("Name:") {
+                name = value.trim().to_string();
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') || line.starts_with("Columns:") {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let mut channel = || {
+                parts
+                    .next()
+                    .and_then(|i| i.parse::<u8>().ok())
+                    .ok_or_else(|| format!("{} has an invalid line: {}", path, line))
+            };
+            let (r, g, b) = (channel()?, channel()?, channel()?);
+            let (color, role) = match parts.next().and_then(EditorPalette::parse) {
+                Some(color) => (color, parts.next().and_then(EditorExport::identifier)),
+                None => (Color::from_rgba(r, g, b, 255), None),
+            };
+            swatches.push(EditorSwatch::new(color, role));
+        }
+        EditorPalette::unique_roles(&mut swatches);
+        let index = None;
+        Ok(Self {
+            name,
+            swatches,
+            index,
+        })
+    }
+
+    pub fn save_gpl(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let mut content = String::new();
+        content.push_str("GIMP Palette\n");
+        content.push_str(&format!("Name: {}\n", self.name));
+        content.push_str("Columns: 8\n#\n");
+        for swatch in self.swatches.iter() {
+            let [r, g, b, _]: [u8; 4] = swatch.color.into();
+            let line = format!(
+                "{:3} {:3} {:3}\t{} {}",
+                r,
+                g,
+                b,
+                EditorPalette::hex(swatch.color),
+                swatch.role.as_deref().unwrap_or_default(),
+            );
+            content.push_str(line.trim_end());
+            content.push('\n');
+        }
+        fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> EditorPalette {
+        let mut palette = EditorPalette::new();
+        palette.name = "Test Palette".to_string();
+        palette.swatches = vec![
+            EditorSwatch::new(Color::from_rgba(255, 0, 0, 255), Some("body".to_string())),
+            EditorSwatch::new(Color::from_rgba(0, 128, 255, 64), None),
+            EditorSwatch::new(Color::from_rgba(1, 2, 3, 4), Some("eye".to_string())),
+        ];
+        palette
+    }
+
+    fn path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("{}_{}", std::process::id(), name));
+        path.to_string_lossy().into_owned()
+    }
+
+    fn same(a: &EditorPalette, b: &EditorPalette) {
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.swatches.len(), b.swatches.len());
+        for (a, b) in a.swatches.iter().zip(b.swatches.iter()) {
+            assert_eq!(a.color, b.color);
+            assert_eq!(a.role, b.role);
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let path = path(PALETTE_JSON);
+        palette().save_json(&path).unwrap();
+        let loaded = EditorPalette::load_json(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        same(&palette(), &loaded);
+    }
+
+    #[test]
+    fn gpl_round_trip() {
+        let path = path(PALETTE_GPL);
+        palette().save_gpl(&path).unwrap();
+        let loaded = EditorPalette::load_gpl(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        same(&palette(), &loaded);
+    }
+
+    #[test]
+    fn roles_are_unique() {
+        let mut palette = palette();
+        palette.set_role(1, Some("body".to_string()));
+        assert_eq!(palette.swatches[0].role, None);
+        assert_eq!(palette.role(palette.swatches[0].color), None);
+        assert_eq!(palette.role(palette.swatches[1].color), Some("body"));
+        assert_eq!(
+            palette.roles(),
+            vec![
+                ("body", palette.swatches[1].color),
+                ("eye", palette.swatches[2].color),
+            ]
+        );
+
+        let mut swatches = palette.swatches.clone();
+        swatches[0].role = Some("eye".to_string());
+        EditorPalette::unique_roles(&mut swatches);
+        let roles: Vec<_> = swatches.iter().map(|i| i.role.as_deref()).collect();
+        assert_eq!(roles, vec![Some("eye"), Some("body"), None]);
+    }
+}
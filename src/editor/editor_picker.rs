@@ -0,0 +1,474 @@
+use macroquad::prelude::draw_rectangle;
+use macroquad::prelude::draw_rectangle_lines;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::is_mouse_button_down;
+use macroquad::prelude::is_mouse_button_pressed;
+use macroquad::prelude::is_mouse_button_released;
+use macroquad::prelude::mouse_position;
+use macroquad::prelude::screen_width;
+use macroquad::prelude::Color;
+use macroquad::prelude::MouseButton;
+use macroquad::prelude::Rect;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::BLACK;
+use macroquad::prelude::DARKGRAY;
+use macroquad::prelude::GRAY;
+use macroquad::prelude::GREEN;
+use macroquad::prelude::LIGHTGRAY;
+use macroquad::prelude::YELLOW;
+
+use super::EditorInput;
+use super::EditorInputs;
+use super::EditorPalette;
+use super::EditorState;
+use super::EditorSwatch;
+
+pub const PICKER_WIDTH: f32 = 240.0;
+pub const PICKER_SWATCH: f32 = 30.0;
+pub const PICKER_RECENT: usize = 8;
+
+const SLIDERS: [&str; 7] = ["H", "S", "V", "R", "G", "B", "A"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorPickers {
+    Fill,
+    Stroke,
+    Slider(usize),
+    Hex,
+    Swatch(usize),
+    Add,
+    Remove,
+    Role,
+    Save,
+    Load,
+    Apply,
+    Replace,
+    Recent(usize),
+}
+
+impl EditorPickers {
+    pub fn text(&self) -> &str {
+        match self {
+            EditorPickers::Fill => "FILL",
+            EditorPickers::Stroke => "STROKE",
+            EditorPickers::Slider(i) => SLIDERS[*i],
+            EditorPickers::Hex => "HEX",
+            EditorPickers::Add => "ADD",
+            EditorPickers::Remove => "DEL",
+            EditorPickers::Role => "ROLE",
+            EditorPickers::Save => "SAVE",
+            EditorPickers::Load => "LOAD",
+            EditorPickers::Apply => "APPLY",
+            EditorPickers::Replace => "REPLACE",
+            EditorPickers::Swatch(_) | EditorPickers::Recent(_) => "",
+        }
+    }
+}
+
+pub struct EditorPicker {}
+
+impl EditorPicker {
+    pub fn hsv(color: Color) -> (f32, f32, f32) {
+        let max = color.r.max(color.g).max(color.b);
+        let min = color.r.min(color.g).min(color.b);
+        let delta = max - min;
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == color.r {
+            ((color.g - color.b) / delta).rem_euclid(6.0)
+        } else if max == color.g {
+            (color.b - color.r) / delta + 2.0
+        } else {
+            (color.r - color.g) / delta + 4.0
+        };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        (hue / 6.0, saturation, max)
+    }
+
+    pub fn rgb(hue: f32, saturation: f32, value: f32, alpha: f32) -> Color {
+        let hue = hue.rem_euclid(1.0) * 6.0;
+        let f = hue.fract();
+        let p = value * (1.0 - saturation);
+        let q = value * (1.0 - saturation * f);
+        let t = value * (1.0 - saturation * (1.0 - f));
+        let (r, g, b) = match hue as u8 {
+            0 => (value, t, p),
+            1 => (q, value, p),
+            2 => (p, value, t),
+            3 => (p, q, value),
+            4 => (t, p, value),
+            _ => (value, p, q),
+        };
+        Color::new(r, g, b, alpha)
+    }
+
+    /// Hue, saturation and value of the picker color. The ones set with the sliders are kept
+    /// while the color still matches them, so hue survives greys and both survive black.
+    fn picker_hsv(state: &EditorState) -> [f32; 3] {
+        let color = state.picker_color();
+        match state.picker_hsv {
+            Some((rgb, hsv)) if rgb == [color.r, color.g, color.b] => hsv,
+            _ => {
+                let (hue, saturation, value) = EditorPicker::hsv(color);
+                [hue, saturation, value]
+            }
+        }
+    }
+
+    fn slider(state: &EditorState, slider: usize) -> f32 {
+        let color = state.picker_color();
+        let [hue, saturation, value] = EditorPicker::picker_hsv(state);
+        [hue, saturation, value, color.r, color.g, color.b, color.a][slider]
+    }
+
+    fn slider_color(color: Color, hsv: [f32; 3], slider: usize, t: f32) -> Color {
+        let [hue, saturation, value] = hsv;
+        match slider {
+            0 => EditorPicker::rgb(t, saturation, value, color.a),
+            1 => EditorPicker::rgb(hue, t, value, color.a),
+            2 => EditorPicker::rgb(hue, saturation, t, color.a),
+            3 => Color { r: t, ..color },
+            4 => Color { g: t, ..color },
+            5 => Color { b: t, ..color },
+            _ => Color { a: t, ..color },
+        }
+    }
+
+    fn set_slider(state: &mut EditorState, slider: usize, t: f32) {
+        let mut hsv = EditorPicker::picker_hsv(state);
+        let color = EditorPicker::slider_color(state.picker_color(), hsv, slider, t);
+        if slider < hsv.len() {
+            hsv[slider] = t;
+            state.picker_hsv = Some(([color.r, color.g, color.b], hsv));
+        }
+        state.color(color);
+    }
+
+    pub fn list(state: &EditorState) -> Vec<(EditorPickers, Rect)> {
+        let x = screen_width() - PICKER_WIDTH - 10.0;
+        let mut y = 40.0;
+        let mut list = vec![
+            (EditorPickers::Fill, Rect::new(x, y, 60.0, 20.0)),
+            (EditorPickers::Stroke, Rect::new(x + 70.0, y, 80.0, 20.0)),
+        ];
+        y += 30.0;
+        for i in 0..SLIDERS.len() {
+            let rect = Rect::new(x + 20.0, y, PICKER_WIDTH - 20.0, 14.0);
+            list.push((EditorPickers::Slider(i), rect));
+            y += 22.0;
+        }
+        list.push((EditorPickers::Hex, Rect::new(x, y, PICKER_WIDTH, 20.0)));
+        y += 30.0;
+
+        let columns = (PICKER_WIDTH / PICKER_SWATCH) as usize;
+        for i in 0..state.palette.swatches.len() {
+            let column = (i % columns) as f32;
+            let row = (i / columns) as f32;
+            let rect = Rect::new(
+                x + column * PICKER_SWATCH,
+                y + row * PICKER_SWATCH,
+                PICKER_SWATCH - 4.0,
+                PICKER_SWATCH - 4.0,
+            );
+            list.push((EditorPickers::Swatch(i), rect));
+        }
+        let rows = state.palette.swatches.len().div_ceil(columns).max(1);
+        y += rows as f32 * PICKER_SWATCH + 6.0;
+
+        let buttons = [
+            EditorPickers::Add,
+            EditorPickers::Remove,
+            EditorPickers::Role,
+            EditorPickers::Save,
+            EditorPickers::Load,
+        ];
+        let button_width = PICKER_WIDTH / buttons.len() as f32;
+        for (i, button) in buttons.into_iter().enumerate() {
+            let rect = Rect::new(x + i as f32 * button_width, y, button_width - 4.0, 20.0);
+            list.push((button, rect));
+        }
+        y += 24.0;
+
+        let buttons = [EditorPickers::Apply, EditorPickers::Replace];
+        let button_width = PICKER_WIDTH / buttons.len() as f32;
+        for (i, button) in buttons.into_iter().enumerate() {
+            let rect = Rect::new(x + i as f32 * button_width, y, button_width - 4.0, 20.0);
+            list.push((button, rect));
+        }
+        y += 30.0;
+
+        for i in 0..state.palette_recent.len() {
+            let rect = Rect::new(
+                x + i as f32 * PICKER_SWATCH,
+                y,
+                PICKER_SWATCH - 4.0,
+                PICKER_SWATCH - 4.0,
+            );
+            list.push((EditorPickers::Recent(i), rect));
+        }
+        list
+    }
+
+    pub fn find(state: &EditorState) -> Option<EditorPickers> {
+        if !state.picker {
+            return None;
+        }
+        let position: Vec2 = mouse_position().into();
+        EditorPicker::list(state)
+            .into_iter()
+            .find(|(_, rect)| rect.contains(position))
+            .map(|(picker, _)| picker)
+    }
+
+    pub fn hover(state: &EditorState) -> bool {
+        if !state.picker {
+            return false;
+        }
+        let position: Vec2 = mouse_position().into();
+        let list = EditorPicker::list(state);
+        let bottom = list.iter().map(|(_, i)| i.bottom()).fold(0.0, f32::max);
+        let x = screen_width() - PICKER_WIDTH - 20.0;
+        Rect::new(x, 30.0, PICKER_WIDTH + 20.0, bottom - 20.0).contains(position)
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        if !state.picker {
+            return;
+        }
+        let position: Vec2 = mouse_position().into();
+        let color = state.picker_color();
+        let hsv = EditorPicker::picker_hsv(state);
+        let x = screen_width() - PICKER_WIDTH - 20.0;
+        let list = EditorPicker::list(state);
+        let bottom = list.iter().map(|(_, i)| i.bottom()).fold(0.0, f32::max);
+        draw_rectangle(
+            x,
+            30.0,
+            PICKER_WIDTH + 20.0,
+            bottom - 20.0,
+            BLACK.with_alpha(0.8),
+        );
+
+        for (picker, rect) in list {
+            let is_position = rect.contains(position);
+            match picker {
+                EditorPickers::Fill | EditorPickers::Stroke => {
+                    let active = (picker == EditorPickers::Stroke) == state.picker_stroke;
+                    let text_color = if is_position {
+                        LIGHTGRAY
+                    } else if active {
+                        GREEN
+                    } else {
+                        GRAY
+                    };
+                    draw_text(picker.text(), rect.x, rect.bottom(), 21.0, text_color);
+                }
+                EditorPickers::Slider(i) => {
+                    let steps = 24;
+                    let step = rect.w / steps as f32;
+                    for s in 0..steps {
+                        let t = (s as f32 + 0.5) / steps as f32;
+                        let step_color = EditorPicker::slider_color(color, hsv, i, t);
+                        let step_x = rect.x + s as f32 * step;
+                        draw_rectangle(step_x, rect.y, step + 0.5, rect.h, step_color);
+                    }
+                    let value = EditorPicker::slider(state, i);
+                    let value_x = rect.x + value * rect.w;
+                    draw_rectangle_lines(
+                        value_x - 2.0,
+                        rect.y - 2.0,
+                        4.0,
+                        rect.h + 4.0,
+                        2.0,
+                        YELLOW,
+                    );
+                    draw_text(picker.text(), rect.x - 20.0, rect.bottom(), 18.0, GRAY);
+                }
+                EditorPickers::Hex => {
+                    let text = EditorPalette::hex(color);
+                    let text_color = if is_position { LIGHTGRAY } else { GRAY };
+                    draw_text(picker.text(), rect.x, rect.bottom() - 4.0, 18.0, text_color);
+                    draw_text(&text, rect.x + 40.0, rect.bottom() - 4.0, 18.0, text_color);
+                    draw_rectangle(rect.right() - 40.0, rect.y, 40.0, rect.h, color);
+                }
+                EditorPickers::Swatch(i) => {
+                    let swatch = &state.palette.swatches[i];
+                    draw_rectangle(rect.x, rect.y, rect.w, rect.h, swatch.color);
+                    if let Some(role) = &swatch.role {
+                        let initial = &role[..1].to_uppercase();
+                        draw_text(initial, rect.x + 2.0, rect.bottom() - 2.0, 16.0, BLACK);
+                    }
+                    if state.palette.index == Some(i) {
+                        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2.0, YELLOW);
+                    } else if is_position {
+                        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 1.0, LIGHTGRAY);
+                    }
+                }
+                EditorPickers::Recent(i) => {
+                    let swatch = state.palette_recent[i];
+                    draw_rectangle(rect.x, rect.y, rect.w, rect.h, swatch);
+                    if is_position {
+                        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 1.0, LIGHTGRAY);
+                    }
+                }
+                EditorPickers::Add
+                | EditorPickers::Remove
+                | EditorPickers::Role
+                | EditorPickers::Save
+                | EditorPickers::Load
+                | EditorPickers::Apply
+                | EditorPickers::Replace => {
+                    let disabled = match picker {
+                        EditorPickers::Remove | EditorPickers::Role => {
+                            state.palette.index.is_none()
+                        }
+                        EditorPickers::Apply | EditorPickers::Replace => state.selection.is_empty(),
+                        _ => false,
+                    };
+                    let text_color = if disabled {
+                        DARKGRAY
+                    } else if is_position {
+                        LIGHTGRAY
+                    } else {
+                        GRAY
+                    };
+                    draw_text(picker.text(), rect.x, rect.bottom() - 4.0, 18.0, text_color);
+                }
+            }
+        }
+    }
+
+    pub fn actions(state: &mut EditorState) {
+        if !state.picker {
+            state.picker_slider = None;
+            return;
+        }
+        let position: Vec2 = mouse_position().into();
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            if let Some(picker) = EditorPicker::find(state) {
+                match picker {
+                    EditorPickers::Fill => {
+                        state.picker_stroke = false;
+                    }
+                    EditorPickers::Stroke => {
+                        state.picker_stroke = true;
+                    }
+                    EditorPickers::Slider(i) => {
+                        state.picker_slider = Some(i);
+                    }
+                    EditorPickers::Hex => {
+                        let text = EditorPalette::hex(state.picker_color());
+                        state.input = Some(EditorInput::new(EditorInputs::Hex, text));
+                    }
+                    EditorPickers::Swatch(i) => {
+                        let color = state.palette.swatches[i].color;
+                        state.palette.index = Some(i);
+                        state.color(color);
+                        state.color_recent(color);
+                    }
+                    EditorPickers::Recent(i) => {
+                        let color = state.palette_recent[i];
+                        state.color(color);
+                        state.color_recent(color);
+                    }
+                    EditorPickers::Add => {
+                        let swatch = EditorSwatch::new(state.picker_color(), None);
+                        state.palette.swatches.push(swatch);
+                        state.palette.index = Some(state.palette.swatches.len() - 1);
+                    }
+                    EditorPickers::Remove => {
+                        if let Some(i) = state.palette.index.take() {
+                            state.palette.swatches.remove(i);
+                        }
+                    }
+                    EditorPickers::Role => {
+                        if let Some(i) = state.palette.index {
+                            let role = state.palette.swatches[i].role.clone();
+                            let input =
+                                EditorInput::new(EditorInputs::Role, role.unwrap_or_default());
+                            state.input = Some(input);
+                        }
+                    }
+                    EditorPickers::Save => {
+                        if let Err(e) = state.palette.save() {
+                            eprintln!("Failed to save palette: {e}");
+                        }
+                    }
+                    EditorPickers::Apply => {
+                        state.recolor();
+                    }
+                    EditorPickers::Replace => {
+                        if let Some(color) = state.selection_color() {
+                            state.replace(color, state.picker_color());
+                        }
+                    }
+                    EditorPickers::Load => match EditorPalette::load() {
+                        Ok(palette) => state.set_palette(palette),
+                        Err(e) => eprintln!("Failed to load palette: {e}"),
+                    },
+                }
+            }
+        }
+
+        if let Some(slider) = state.picker_slider {
+            if is_mouse_button_down(MouseButton::Left) {
+                let list = EditorPicker::list(state);
+                if let Some((_, rect)) = list
+                    .iter()
+                    .find(|(picker, _)| *picker == EditorPickers::Slider(slider))
+                {
+                    let t = ((position.x - rect.x) / rect.w).clamp(0.0, 1.0);
+                    EditorPicker::set_slider(state, slider, t);
+                }
+            }
+            if is_mouse_button_released(MouseButton::Left) {
+                state.picker_slider = None;
+                state.color_recent(state.picker_color());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Color;
+
+    use super::EditorPicker;
+    use super::EditorState;
+
+    #[test]
+    fn hsv_round_trip() {
+        for color in [
+            Color::new(1.0, 0.0, 0.0, 1.0),
+            Color::new(0.2, 0.6, 0.4, 0.5),
+            Color::new(0.9, 0.1, 0.7, 1.0),
+            Color::new(0.5, 0.5, 0.5, 1.0),
+        ] {
+            let (hue, saturation, value) = EditorPicker::hsv(color);
+            let rgb = EditorPicker::rgb(hue, saturation, value, color.a);
+            for (a, b) in [(rgb.r, color.r), (rgb.g, color.g), (rgb.b, color.b)] {
+                assert!((a - b).abs() < 1e-5, "{:?} became {:?}", color, rgb);
+            }
+        }
+    }
+
+    #[test]
+    fn sliders_keep_hue_at_zero_saturation_and_value() {
+        let mut state = EditorState::new();
+        state.color(Color::new(1.0, 0.0, 0.0, 1.0));
+        EditorPicker::set_slider(&mut state, 0, 0.5);
+        EditorPicker::set_slider(&mut state, 1, 0.0);
+        assert_eq!(EditorPicker::picker_hsv(&state), [0.5, 0.0, 1.0]);
+        EditorPicker::set_slider(&mut state, 2, 0.0);
+        assert_eq!(EditorPicker::picker_hsv(&state), [0.5, 0.0, 0.0]);
+        EditorPicker::set_slider(&mut state, 1, 1.0);
+        EditorPicker::set_slider(&mut state, 2, 1.0);
+        let color = state.picker_color();
+        assert_eq!([color.r, color.g, color.b], [0.0, 1.0, 1.0]);
+
+        // A color set elsewhere is read back from its RGB.
+        state.color(Color::new(0.0, 0.0, 1.0, 1.0));
+        assert_eq!(EditorPicker::picker_hsv(&state), [4.0 / 6.0, 1.0, 1.0]);
+    }
+}
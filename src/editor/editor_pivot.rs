@@ -0,0 +1,94 @@
+use macroquad::prelude::draw_circle_lines;
+use macroquad::prelude::draw_line;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::Rect;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::ORANGE;
+
+use super::EditorExport;
+use super::EditorState;
+use super::EditorValues;
+
+pub const PIVOT_SIZE: f32 = 6.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditorPivots {
+    TopLeft,
+    Center,
+    /// Placed on the canvas, in canvas coordinates.
+    Custom(Vec2),
+}
+
+impl EditorPivots {
+    pub fn text(&self) -> &str {
+        match self {
+            EditorPivots::TopLeft => "PIVOT TOP LEFT",
+            EditorPivots::Center => "PIVOT CENTER",
+            EditorPivots::Custom(_) => "PIVOT CUSTOM",
+        }
+    }
+}
+
+/// Origin of the exported coordinates, so the shape can be drawn and rotated around it in-game.
+pub struct EditorPivot {}
+
+impl EditorPivot {
+    /// Bounds of the exported shapes, markers and hitboxes left out.
+    pub fn bounds(state: &EditorState) -> Rect {
+        EditorExport::elements(state)
+            .into_iter()
+            .map(|i| state.stack[i])
+            .filter(|i| !i.hitbox && !matches!(i.value, EditorValues::Marker { .. }))
+            .map(|i| i.value.bounds())
+            .reduce(|a, b| a.combine_with(b))
+            .unwrap_or_default()
+    }
+
+    pub fn point(state: &EditorState) -> Vec2 {
+        match state.pivot {
+            EditorPivots::TopLeft => EditorPivot::bounds(state).point(),
+            EditorPivots::Center => EditorPivot::bounds(state).center(),
+            EditorPivots::Custom(point) => point,
+        }
+    }
+
+    /// Switches between the top-left and the center of the bounds.
+    pub fn next(state: &mut EditorState) {
+        state.pivot = match state.pivot {
+            EditorPivots::TopLeft => EditorPivots::Center,
+            EditorPivots::Center | EditorPivots::Custom(_) => EditorPivots::TopLeft,
+        };
+    }
+
+    /// Places a custom pivot at the snapped cursor.
+    pub fn place(state: &mut EditorState) {
+        state.pivot = EditorPivots::Custom(state.position());
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        if EditorExport::elements(state).is_empty() {
+            return;
+        }
+        let point = EditorPivot::point(state);
+        let size = PIVOT_SIZE * 2.0;
+        draw_circle_lines(point.x, point.y, PIVOT_SIZE, 1.0, ORANGE);
+        draw_line(
+            point.x - size,
+            point.y,
+            point.x + size,
+            point.y,
+            1.0,
+            ORANGE,
+        );
+        draw_line(
+            point.x,
+            point.y - size,
+            point.x,
+            point.y + size,
+            1.0,
+            ORANGE,
+        );
+        let text = state.pivot.text();
+        draw_text(text, point.x + size, point.y - size, 14.0, ORANGE);
+    }
+}
@@ -0,0 +1,118 @@
+use macroquad::prelude::draw_circle_lines;
+use macroquad::prelude::draw_line;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::DARKGRAY;
+use macroquad::prelude::GREEN;
+
+use super::EditorElement;
+use super::EditorInputs;
+use super::EditorState;
+
+pub const REPEAT_MAX: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorRepeats {
+    /// Copies stepped along a vector.
+    Linear,
+    /// Copies turned around `state.repeat_pivot` by an angle step.
+    Radial,
+}
+
+impl EditorRepeats {
+    pub fn text(&self) -> &str {
+        match self {
+            EditorRepeats::Linear => "REPEAT COUNT DX DY",
+            EditorRepeats::Radial => "REPEAT COUNT DEGREES",
+        }
+    }
+
+    pub fn default(&self) -> String {
+        match self {
+            EditorRepeats::Linear => "3 20 0".to_string(),
+            EditorRepeats::Radial => "5 60".to_string(),
+        }
+    }
+}
+
+/// Repeats the selection from typed parameters, previewed while they are being typed.
+pub struct EditorRepeat {}
+
+impl EditorRepeat {
+    /// Copies of the selection for the typed parameters, numbers separated by spaces or commas.
+    pub fn copies(
+        state: &EditorState,
+        repeat: EditorRepeats,
+        text: &str,
+    ) -> Result<Vec<EditorElement>, String> {
+        let numbers: Vec<f32> = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|i| !i.is_empty())
+            .map(|i| i.parse::<f32>().ok().filter(|i| i.is_finite()))
+            .collect::<Option<_>>()
+            .ok_or("EXPECTED NUMBERS")?;
+        let count = match numbers.first() {
+            Some(&count) if count >= 1.0 && count <= REPEAT_MAX as f32 && count.fract() == 0.0 => {
+                count as usize
+            }
+            _ => return Err(format!("COUNT FROM 1 TO {}", REPEAT_MAX)),
+        };
+        let mut copies = Vec::new();
+        match (repeat, &numbers[1..]) {
+            (EditorRepeats::Linear, &[x, y]) => {
+                for n in 1..=count {
+                    for &i in state.selection.iter() {
+                        let mut copy = state.stack[i];
+                        copy.value.translate(Vec2::new(x, y) * n as f32);
+                        copies.push(copy);
+                    }
+                }
+            }
+            (EditorRepeats::Radial, &[degrees]) => {
+                for n in 1..=count {
+                    for &i in state.selection.iter() {
+                        let mut copy = state.stack[i];
+                        let angle = (degrees * n as f32).to_radians();
+                        copy.value.rotate(state.repeat_pivot, angle);
+                        copies.push(copy);
+                    }
+                }
+            }
+            (EditorRepeats::Linear, _) => return Err("EXPECTED COUNT DX DY".to_string()),
+            (EditorRepeats::Radial, _) => return Err("EXPECTED COUNT DEGREES".to_string()),
+        }
+        for copy in copies.iter_mut() {
+            copy.link = None;
+        }
+        Ok(copies)
+    }
+
+    /// Adds the copies as one undo step and selects them along with the originals.
+    pub fn apply(state: &mut EditorState, repeat: EditorRepeats, text: &str) -> Result<(), String> {
+        let copies = EditorRepeat::copies(state, repeat, text)?;
+        state.save();
+        let start = state.stack.len();
+        state.stack.extend(copies);
+        state.selection.extend(start..state.stack.len());
+        Ok(())
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        let Some(input) = &state.input else {
+            return;
+        };
+        let EditorInputs::Repeat(repeat) = input.input else {
+            return;
+        };
+        if repeat == EditorRepeats::Radial {
+            let pivot = state.repeat_pivot;
+            draw_circle_lines(pivot.x, pivot.y, 4.0, 1.0, GREEN);
+            draw_line(pivot.x - 8.0, pivot.y, pivot.x + 8.0, pivot.y, 1.0, GREEN);
+            draw_line(pivot.x, pivot.y - 8.0, pivot.x, pivot.y + 8.0, 1.0, GREEN);
+        }
+        if let Ok(copies) = EditorRepeat::copies(state, repeat, &input.text) {
+            for copy in copies {
+                copy.draw(Some(DARKGRAY));
+            }
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use macroquad::prelude::draw_line;
+use macroquad::prelude::get_time;
 use macroquad::prelude::mouse_position;
 use macroquad::prelude::screen_height;
 use macroquad::prelude::screen_width;
 use macroquad::prelude::Color;
-use macroquad::prelude::DrawRectangleParams;
+use macroquad::prelude::Rect;
 use macroquad::prelude::Vec2;
 use macroquad::prelude::WHITE;
 use macroquad::prelude::YELLOW;
@@ -11,7 +12,18 @@ use macroquad::prelude::YELLOW;
 use super::EditorButtons;
 use super::EditorElement;
 use super::EditorElements;
+use super::EditorExport;
+use super::EditorGroup;
+use super::EditorInput;
+use super::EditorLayer;
+use super::EditorMetrics;
+use super::EditorPalette;
+use super::EditorPivots;
+use super::EditorStyle;
+use super::EditorSymmetries;
+use super::EditorTransforms;
 use super::EditorValues;
+use super::PICKER_RECENT;
 
 pub const DISPLAY_SIZE: Vec2 = Vec2::new(640.0, 480.0);
 pub const DISPLAY_SIZE_HD: Vec2 = Vec2::new(1280.0, 720.0);
@@ -22,19 +34,53 @@ pub const STICKY_ELEMENT: f32 = 5.0;
 pub const SIZE_GRID: f32 = 10.0;
 pub const SIZE_POINT: f32 = 3.0;
 
+pub const THICKNESS_STEP: f32 = 0.5;
+pub const THICKNESS_MIN: f32 = 0.5;
+pub const THICKNESS_MAX: f32 = 20.0;
+/// Seconds between wheel ticks or clicks that still change the thickness in the same step.
+pub const THICKNESS_BURST: f64 = 1.0;
+
 #[derive(Debug, Clone)]
 pub struct EditorState {
     // pub element: Option<EditorElement>,
     pub element: EditorElements,
     pub element_thickness: f32,
+    pub element_fill: bool,
     pub element_color: Color,
-    pub element_color_index: usize,
-    // pub element_lines: bool,
+    pub element_stroke: bool,
+    pub element_stroke_color: Color,
     pub stack: Vec<EditorElement>,
     pub stack_undo: Vec<Vec<EditorElement>>,
     pub stack_redo: Vec<Vec<EditorElement>>,
+    pub stack_previous: Vec<EditorElement>,
+    pub stack_pending: Option<Vec<EditorElement>>,
+    /// Undo depth and time of the last thickness change, so a burst of them is one undo step.
+    pub stack_thickness: Option<(usize, f64)>,
+
+    pub selection: Vec<usize>,
+    pub groups: Vec<EditorGroup>,
+    pub group: Option<usize>,
+    pub click: Option<(f64, Vec2)>,
+    pub layers: Vec<EditorLayer>,
+    pub layer: usize,
+    pub layer_panel: bool,
+    pub export_hidden: bool,
+    /// Aligns to the HD display frame instead of the SD one.
+    pub display_hd: bool,
+    pub clipboard: String,
+    pub clipboard_count: usize,
+    pub repeat_pivot: Vec2,
+    pub names: Vec<String>,
+    pub pivot: EditorPivots,
+    pub hitbox: bool,
+    pub metrics_panel: bool,
+    pub metrics_elements: Vec<EditorElement>,
+    pub metrics: Option<EditorMetrics>,
+    pub tags: Vec<String>,
 
     pub current: Option<Vec2>,
+    /// Points clicked so far for a new polygon.
+    pub polygon: Vec<Vec2>,
 
     pub button: Option<EditorButtons>,
 
@@ -42,9 +88,29 @@ pub struct EditorState {
     pub snap: bool,
     pub grid: u16,
     pub help: bool,
+    pub vertex: bool,
+    pub connect: bool,
+    pub symmetry: Option<EditorSymmetries>,
 
     pub drag: bool,
-    pub drag_offset: Option<Vec2>,
+    pub drag_position: Option<Vec2>,
+    pub drag_transform: Option<EditorTransforms>,
+    pub drag_pivot: Option<Vec2>,
+    pub drag_rotation: Option<f32>,
+    pub drag_bounds: Option<Rect>,
+    pub drag_points: Vec<(usize, usize)>, // (element index, point index)
+    pub drag_original: Vec<EditorElement>,
+
+    pub input: Option<EditorInput>,
+
+    pub picker: bool,
+    pub picker_stroke: bool,
+    pub picker_slider: Option<usize>,
+    /// Hue, saturation and value last set with the sliders, with the RGB they produced.
+    pub picker_hsv: Option<([f32; 3], [f32; 3])>,
+
+    pub palette: EditorPalette,
+    pub palette_recent: Vec<Color>,
     // pub position_cursor: Option<Vec2>,
     // pub button:
     // pub thickness: f32,
@@ -53,10 +119,6 @@ pub struct EditorState {
     // pub current_start: Option<Vec2>,
     // pub sticky_radius: f32,
 
-    // pub dragging_point: Option<(usize, bool)>, // (line index, is_start)
-    // pub drag_start_mouse_pos: Option<Vec2>,    // Mouse pos when drag started
-    // pub drag_original_positions: Vec<(usize, bool, Vec2)>, // All matching points original positions
-
     // pub show_points: bool,
 }
 
@@ -66,13 +128,37 @@ impl EditorState {
             stack: Vec::new(),
             stack_undo: Vec::new(),
             stack_redo: Vec::new(),
+            stack_previous: Vec::new(),
+            stack_pending: None,
+            stack_thickness: None,
+            selection: Vec::new(),
+            groups: Vec::new(),
+            group: None,
+            click: None,
+            layers: vec![EditorLayer::new(0)],
+            layer: 0,
+            layer_panel: false,
+            export_hidden: false,
+            display_hd: false,
+            clipboard: String::new(),
+            clipboard_count: 0,
+            repeat_pivot: Vec2::ZERO,
+            names: Vec::new(),
+            pivot: EditorPivots::TopLeft,
+            hitbox: false,
+            metrics_panel: false,
+            metrics_elements: Vec::new(),
+            metrics: None,
+            tags: Vec::new(),
             // element: None,
             element: EditorElements::Line,
             element_thickness: 1.0,
+            element_fill: true,
             element_color: WHITE.with_alpha(0.5),
-            element_color_index: 0,
-            // element_lines: false,
+            element_stroke: false,
+            element_stroke_color: WHITE,
             current: None,
+            polygon: Vec::new(),
             // select
             button: Some(EditorButtons::Line),
 
@@ -80,17 +166,32 @@ impl EditorState {
             snap: true,
             grid: 2,
             help: false,
+            vertex: false,
+            connect: true,
+            symmetry: None,
 
             drag: false,
-            drag_offset: None,
+            drag_position: None,
+            drag_transform: None,
+            drag_pivot: None,
+            drag_rotation: None,
+            drag_bounds: None,
+            drag_points: Vec::new(),
+            drag_original: Vec::new(),
+
+            input: None,
+
+            picker: false,
+            picker_stroke: false,
+            picker_slider: None,
+            picker_hsv: None,
+
+            palette: EditorPalette::new(),
+            palette_recent: Vec::new(),
             // cursor: None,
             // current_start: None,
             // sticky_radius: 10.0,
 
-            // dragging_point: None,
-            // drag_start_mouse_pos: None,
-            // drag_original_positions: Vec::new(),
-
             // undo_stack: Vec::new(),
             // redo_stack: Vec::new(),
 
@@ -98,275 +199,286 @@ impl EditorState {
         }
     }
 
+    pub fn style(&self) -> EditorStyle {
+        let fill = self.element_fill.then_some(self.element_color);
+        let stroke = self.element_stroke.then_some(self.element_stroke_color);
+        let thickness = self.element_thickness;
+        EditorStyle {
+            fill,
+            stroke,
+            thickness,
+        }
+    }
+
+    pub fn picker_color(&self) -> Color {
+        if self.picker_stroke {
+            self.element_stroke_color
+        } else {
+            self.element_color
+        }
+    }
+
+    pub fn color(&mut self, color: Color) {
+        if self.picker_stroke {
+            self.element_stroke_color = color;
+        } else {
+            self.element_color = color;
+        }
+    }
+
+    pub fn color_recent(&mut self, color: Color) {
+        self.palette_recent.retain(|&i| i != color);
+        self.palette_recent.insert(0, color);
+        self.palette_recent.truncate(PICKER_RECENT);
+    }
+
+    pub fn selection_bounds(&self) -> Option<Rect> {
+        self.selection
+            .iter()
+            .map(|&i| self.stack[i].value.bounds())
+            .reduce(|a, b| a.combine_with(b))
+    }
+
+    pub fn rotate(&mut self, angle: f32) {
+        let Some(bounds) = self.selection_bounds() else {
+            return;
+        };
+        self.save();
+        let pivot = bounds.center();
+        for &i in self.selection.iter() {
+            self.stack[i].value.rotate(pivot, angle);
+        }
+    }
+
+    pub fn translate(&mut self, offset: Vec2) {
+        if self.selection.is_empty() {
+            return;
+        }
+        self.save();
+        for &i in self.selection.iter() {
+            self.stack[i].value.translate(offset);
+        }
+    }
+
+    /// Moves the selection one step forward or backward in drawing order, or all the way to the
+    /// front or back with `end`. A step passes the next element on the same layer, since those
+    /// are the only ones it can overlap with.
+    pub fn arrange(&mut self, forward: bool, end: bool) {
+        if self.selection.is_empty() {
+            return;
+        }
+        let selection = self.selection.clone();
+        let selected = |i: &usize| selection.contains(i);
+        let mut order: Vec<usize> = (0..self.stack.len()).collect();
+        if end {
+            let (mut other, mut selection): (Vec<usize>, Vec<usize>) =
+                order.into_iter().partition(|i| !selected(i));
+            order = if forward {
+                other.append(&mut selection);
+                other
+            } else {
+                selection.append(&mut other);
+                selection
+            };
+        } else if forward {
+            for position in (0..order.len()).rev() {
+                let index = order[position];
+                let layer = self.stack[index].layer;
+                let next =
+                    (position + 1..order.len()).find(|&i| self.stack[order[i]].layer == layer);
+                if let Some(next) = next.filter(|&i| selected(&index) && !selected(&order[i])) {
+                    order.remove(position);
+                    order.insert(next, index);
+                }
+            }
+        } else {
+            for position in 0..order.len() {
+                let index = order[position];
+                let layer = self.stack[index].layer;
+                let previous = (0..position)
+                    .rev()
+                    .find(|&i| self.stack[order[i]].layer == layer);
+                if let Some(previous) =
+                    previous.filter(|&i| selected(&index) && !selected(&order[i]))
+                {
+                    order.remove(position);
+                    order.insert(previous, index);
+                }
+            }
+        }
+        if order.iter().enumerate().all(|(position, &i)| position == i) {
+            return;
+        }
+        self.save();
+        self.stack = order.iter().map(|&i| self.stack[i]).collect();
+        self.selection = (0..order.len()).filter(|i| selected(&order[*i])).collect();
+    }
+
+    /// Mirrors the selection left to right, or top to bottom, across the center of its bounds
+    /// or across the center line of the display frames.
+    pub fn flip(&mut self, horizontal: bool, display: bool) {
+        let Some(bounds) = self.selection_bounds() else {
+            return;
+        };
+        self.save();
+        let pivot = if display {
+            Vec2::new(screen_width(), screen_height()) / 2.0
+        } else {
+            bounds.center()
+        };
+        let factor = if horizontal {
+            Vec2::new(-1.0, 1.0)
+        } else {
+            Vec2::new(1.0, -1.0)
+        };
+        for &i in self.selection.iter() {
+            self.stack[i].value.scale(pivot, factor);
+        }
+    }
+
+    pub fn selection_color(&self) -> Option<Color> {
+        let element = self.stack.get(*self.selection.first()?)?;
+        match element.value {
+            EditorValues::Line { .. } => element.style.stroke,
+            _ if self.picker_stroke => element.style.stroke,
+            _ => element.style.fill,
+        }
+    }
+
+    pub fn recolor(&mut self) {
+        if self.selection.is_empty() {
+            return;
+        }
+        let color = self.picker_color();
+        self.save();
+        for &i in self.selection.iter() {
+            let element = &mut self.stack[i];
+            match element.value {
+                EditorValues::Line { .. } => element.style.stroke = Some(color),
+                _ if self.picker_stroke => element.style.stroke = Some(color),
+                _ => element.style.fill = Some(color),
+            }
+        }
+    }
+
+    /// Switches palettes. Elements painted with the colour of a role take the colour the role
+    /// has in the new palette, as one undo step, so they keep following the role.
+    pub fn set_palette(&mut self, palette: EditorPalette) {
+        let roles = palette.roles();
+        let changes: Vec<(Color, Color)> = self
+            .palette
+            .roles()
+            .into_iter()
+            .filter_map(|(role, from)| {
+                let to = roles.iter().find(|(i, _)| *i == role)?.1;
+                (from != to).then_some((from, to))
+            })
+            .collect();
+        self.begin();
+        for element in self.stack.iter_mut() {
+            let style = &mut element.style;
+            for color in [&mut style.fill, &mut style.stroke].into_iter().flatten() {
+                if let Some(&(_, to)) = changes.iter().find(|(from, _)| from == color) {
+                    *color = to;
+                }
+            }
+        }
+        self.commit();
+        self.palette = palette;
+    }
+
+    pub fn replace(&mut self, from: Color, to: Color) {
+        if from == to {
+            return;
+        }
+        self.begin();
+        for element in self.stack.iter_mut() {
+            let style = &mut element.style;
+            for color in [&mut style.fill, &mut style.stroke].into_iter().flatten() {
+                if *color == from {
+                    *color = to;
+                }
+            }
+        }
+        self.commit();
+    }
+
+    pub fn thickness(&mut self, delta: f32) {
+        self.element_thickness =
+            (self.element_thickness + delta).clamp(THICKNESS_MIN, THICKNESS_MAX);
+        let thickness = self.element_thickness;
+        if self
+            .selection
+            .iter()
+            .all(|&i| self.stack[i].style.thickness == thickness)
+        {
+            return;
+        }
+        let time = get_time();
+        let burst = self.stack_thickness.is_some_and(|(depth, last)| {
+            depth == self.stack_undo.len() && time - last < THICKNESS_BURST
+        });
+        if !burst {
+            self.begin();
+        }
+        for &i in self.selection.iter() {
+            self.stack[i].style.thickness = thickness;
+        }
+        if !burst {
+            self.commit();
+        }
+        self.stack_thickness = Some((self.stack_undo.len(), time));
+    }
+
+    /// Toggles vertex mode, which only edits existing elements so it leaves draw mode.
+    pub fn vertex(&mut self) {
+        self.vertex = !self.vertex || self.draw;
+        if self.vertex {
+            self.draw = false;
+        }
+    }
+
     pub fn save(&mut self) {
         self.stack_undo.push(self.stack.clone());
         self.stack_redo.clear();
+        self.stack_thickness = None;
+    }
+
+    /// Snapshot taken when an edit starts, kept as an undo step by `commit` only if the edit
+    /// changed the stack, so a click without a move leaves the redo history alone.
+    pub fn begin(&mut self) {
+        self.stack_pending = Some(self.stack.clone());
+    }
+
+    pub fn commit(&mut self) {
+        if let Some(stack) = self.stack_pending.take() {
+            if stack != self.stack {
+                self.stack_undo.push(stack);
+                self.stack_redo.clear();
+            }
+        }
     }
 
     pub fn undo(&mut self) {
+        self.stack_thickness = None;
         if let Some(stack) = self.stack_undo.pop() {
             self.stack_redo.push(self.stack.clone());
             self.stack = stack;
+            self.selection.clear();
         }
     }
 
     pub fn redo(&mut self) {
+        self.stack_thickness = None;
         if let Some(stack) = self.stack_redo.pop() {
             self.stack_undo.push(self.stack.clone());
             self.stack = stack;
+            self.selection.clear();
         }
     }
 
     pub fn export(&self) {
-        let mut content = String::new();
-
-        let mut min_x = f32::MAX;
-        let mut min_y = f32::MAX;
-        let mut max_x = f32::MIN;
-        let mut max_y = f32::MIN;
-
-        for i in &self.stack {
-            match i.value {
-                EditorValues::Line {
-                    point_a, point_b, ..
-                } => {
-                    min_x = min_x.min(point_a.x.min(point_b.x));
-                    min_y = min_y.min(point_a.y.min(point_b.y));
-                    max_x = max_x.max(point_a.x.max(point_b.x));
-                    max_y = max_y.max(point_a.y.max(point_b.y));
-                }
-                EditorValues::Circle { center, radius }
-                | EditorValues::CircleLine { center, radius } => {
-                    min_x = min_x.min(center.x - radius);
-                    min_y = min_y.min(center.y - radius);
-                    max_x = max_x.max(center.x + radius);
-                    max_y = max_y.max(center.y + radius);
-                }
-                EditorValues::Hexagon { center, radius, .. } => {
-                    min_x = min_x.min(center.x - radius);
-                    min_y = min_y.min(center.y - radius);
-                    max_x = max_x.max(center.x + radius);
-                    max_y = max_y.max(center.y + radius);
-                }
-                EditorValues::Ellipse {
-                    center,
-                    width,
-                    height,
-                    rotation,
-                }
-                | EditorValues::EllipseLine {
-                    center,
-                    width,
-                    height,
-                    rotation,
-                } => {
-                    let hw = width / 2.0;
-                    let hh = height / 2.0;
-                    let cos_r = rotation.cos();
-                    let sin_r = rotation.sin();
-
-                    let corners = [
-                        Vec2::new(-hw, -hh),
-                        Vec2::new(hw, -hh),
-                        Vec2::new(hw, hh),
-                        Vec2::new(-hw, hh),
-                    ];
-
-                    for corner in corners.iter() {
-                        let rotated = Vec2::new(
-                            corner.x * cos_r - corner.y * sin_r,
-                            corner.x * sin_r + corner.y * cos_r,
-                        );
-                        let world = center + rotated;
-
-                        min_x = min_x.min(world.x);
-                        min_y = min_y.min(world.y);
-                        max_x = max_x.max(world.x);
-                        max_y = max_y.max(world.y);
-                    }
-                }
-                EditorValues::Rectangle {
-                    point,
-                    width,
-                    height,
-                    rotation,
-                } => {
-                    if rotation == 0.0 {
-                        // Simple case for non-rotated rectangles
-                        min_x = min_x.min(point.x);
-                        min_y = min_y.min(point.y);
-                        max_x = max_x.max(point.x + width);
-                        max_y = max_y.max(point.y + height);
-                    } else {
-                        // Handle rotated rectangles
-                        let cos_r = rotation.cos();
-                        let sin_r = rotation.sin();
-
-                        let corners = [
-                            Vec2::new(0.0, 0.0),
-                            Vec2::new(width, 0.0),
-                            Vec2::new(width, height),
-                            Vec2::new(0.0, height),
-                        ];
-
-                        for corner in corners.iter() {
-                            let rotated = Vec2::new(
-                                corner.x * cos_r - corner.y * sin_r,
-                                corner.x * sin_r + corner.y * cos_r,
-                            );
-                            let world = point + rotated;
-
-                            min_x = min_x.min(world.x);
-                            min_y = min_y.min(world.y);
-                            max_x = max_x.max(world.x);
-                            max_y = max_y.max(world.y);
-                        }
-                    }
-                }
-                EditorValues::Triangle {
-                    point_a,
-                    point_b,
-                    point_c,
-                } => {
-                    min_x = min_x.min(point_a.x.min(point_b.x.min(point_c.x)));
-                    min_y = min_y.min(point_a.y.min(point_b.y.min(point_c.y)));
-                    max_x = max_x.max(point_a.x.max(point_b.x.max(point_c.x)));
-                    max_y = max_y.max(point_a.y.max(point_b.y.max(point_c.y)));
-                }
-            }
-        }
-        let width = max_x - min_x;
-        let height = max_y - min_y;
-
-        content.push_str("fn draw(x: f32, y: f32) {\n");
-        content.push_str(&format!(
-            "   draw_rectangle_lines(x + {:.1}, y + {:.1}, {:.1}, {:.1}, 1.2, {:?});\n",
-            0.0, 0.0, width, height, YELLOW
-        ));
-
-        for i in self.stack.iter() {
-            let color = i.color;
-            match i.value {
-                EditorValues::Line {
-                    point_a,
-                    point_b,
-                    thickness,
-                } => {
-                    content.push_str(&format!(
-                        "   draw_line(x + {:.1}, y + {:.1}, x + {:.1}, y + {:.1}, {:.1}, {:?});\n",
-                        point_a.x - min_x,
-                        point_a.y - min_y,
-                        point_b.x - min_x,
-                        point_b.y - min_y,
-                        thickness,
-                        color,
-                    ));
-                }
-                EditorValues::Circle { center, radius } => {
-                    content.push_str(&format!(
-                        "   draw_circle(x + {:.1}, y + {:.1}, {:.1}, {:?});\n",
-                        center.x - min_x,
-                        center.y - min_y,
-                        radius,
-                        color,
-                    ));
-                }
-                EditorValues::CircleLine { center, radius } => {
-                    content.push_str(&format!(
-                        "   draw_circle_lines(x + {:.1}, y + {:.1}, {:.1}, 1.0, {:?});\n",
-                        center.x - min_x,
-                        center.y - min_y,
-                        radius,
-                        color,
-                    ));
-                }
-                EditorValues::Ellipse {
-                    center,
-                    width,
-                    height,
-                    rotation,
-                } => {
-                    content.push_str(&format!(
-                        "   draw_ellipse(x + {:.1}, y + {:.1}, {:.1}, {:.1}, {:.1}, {:?});\n",
-                        center.x - min_x,
-                        center.y - min_y,
-                        width,
-                        height,
-                        rotation,
-                        color,
-                    ));
-                }
-                EditorValues::EllipseLine {
-                    center,
-                    width,
-                    height,
-                    rotation,
-                } => {
-                    content.push_str(&format!(
-                        "   draw_ellipse_lines(x + {:.1}, y + {:.1}, {:.1}, {:.1}, {:.1}, 1.0, {:?});\n",
-                        center.x - min_x,
-                        center.y - min_y,
-                        width,
-                        height,
-                        rotation,
-                        color,
-                    ));
-                }
-                EditorValues::Rectangle {
-                    width,
-                    height,
-                    rotation,
-                    point,
-                } => {
-                    let params = DrawRectangleParams {
-                        offset: Vec2::new(0.0, 0.0),
-                        rotation,
-                        color,
-                    };
-                    content.push_str(&format!(
-                        "   draw_rectangle_ex(x + {:.1}, y + {:.1}, {:.1}, {:.1}, {:?});\n",
-                        point.x - min_x,
-                        point.y - min_y,
-                        width,
-                        height,
-                        params,
-                    ));
-                }
-                EditorValues::Triangle {
-                    point_a,
-                    point_b,
-                    point_c,
-                } => {
-                    content.push_str(&format!(
-                        "   draw_triangle(Vec2::new(x + {:.1}, y + {:.1}), Vec2::new(x + {:.1}, y + {:.1}), Vec2::new(x + {:.1}, y + {:.1}), {:?});\n",
-                        point_a.x - min_x,
-                        point_a.y - min_y,
-                        point_b.x - min_x,
-                        point_b.y - min_y,
-                        point_c.x - min_x,
-                        point_c.y - min_y,
-                        color,
-                    ));
-                }
-                EditorValues::Hexagon {
-                    center,
-                    radius,
-                    vertical,
-                } => {
-                    content.push_str(&format!(
-                        "   draw_hexagon(x + {:.1}, y + {:.1}, {:.1}, 1.0, {:?}, {:?}, {:?});\n",
-                        center.x - min_x,
-                        center.y - min_y,
-                        radius,
-                        vertical,
-                        color,
-                        color,
-                    ));
-                }
-            }
-        }
-        content.push_str("}\n");
-        println!("\n{}", content);
+        println!("\n{}", EditorExport::export(self));
     }
     pub fn position(&mut self) -> Vec2 {
         let position: Vec2 = mouse_position().into();
@@ -445,7 +557,10 @@ impl EditorState {
                 position_snap.y = height;
             }
 
-            for i in self.stack.iter() {
+            for (index, i) in self.stack.iter().enumerate() {
+                if !EditorLayer::visible(self, index) {
+                    continue;
+                }
                 match i.value {
                     EditorValues::Line {
                         point_a, point_b, ..
@@ -521,6 +636,17 @@ impl EditorState {
                             position_snap.y = point.y;
                         }
                     }
+                    EditorValues::Marker { point } => {
+                        if position.distance(point) <= STICKY_ELEMENT {
+                            position_snap = point;
+                        }
+                        if (position.x - point.x).abs() < SIZE_POINT {
+                            position_snap.x = point.x;
+                        }
+                        if (position.y - point.y).abs() < SIZE_POINT {
+                            position_snap.y = point.y;
+                        }
+                    }
                     _ => {
                         // TODO...
                     }
@@ -533,3 +659,91 @@ impl EditorState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+    use macroquad::prelude::BLUE;
+    use macroquad::prelude::RED;
+
+    use super::EditorElement;
+    use super::EditorState;
+    use super::EditorValues;
+    use crate::editor::EditorSwatch;
+
+    /// A state with one marker per layer entry, told apart by their x.
+    fn state(layers: &[usize]) -> EditorState {
+        let mut state = EditorState::new();
+        for (i, &layer) in layers.iter().enumerate() {
+            let point = Vec2::new(i as f32, 0.0);
+            let mut element = EditorElement::new(EditorValues::Marker { point }, state.style());
+            element.layer = layer;
+            state.stack.push(element);
+        }
+        state
+    }
+
+    fn order(state: &EditorState) -> Vec<usize> {
+        state
+            .stack
+            .iter()
+            .map(|i| i.value.bounds().x as usize)
+            .collect()
+    }
+
+    #[test]
+    fn arrange_steps() {
+        let mut state = state(&[0, 0, 0, 0]);
+        state.selection = vec![0, 2];
+        state.arrange(true, false);
+        assert_eq!(order(&state), vec![1, 0, 3, 2]);
+        assert_eq!(state.selection, vec![1, 3]);
+        assert_eq!(state.stack_undo.len(), 1);
+
+        // Already at the front, nothing changes and no undo step is kept.
+        state.selection = vec![3];
+        state.arrange(true, true);
+        assert_eq!(state.stack_undo.len(), 1);
+
+        state.arrange(false, false);
+        assert_eq!(order(&state), vec![1, 0, 2, 3]);
+        state.arrange(false, true);
+        assert_eq!(order(&state), vec![2, 1, 0, 3]);
+        assert_eq!(state.selection, vec![0]);
+    }
+
+    #[test]
+    fn arrange_passes_elements_on_the_same_layer() {
+        let mut state = state(&[0, 1, 1, 0]);
+        state.selection = vec![0];
+        state.arrange(true, false);
+        assert_eq!(order(&state), vec![1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn replace_without_a_match_keeps_no_undo_step() {
+        let mut state = state(&[0, 0]);
+        let color = state.stack[0].style.fill.unwrap();
+        state.replace(RED, color);
+        assert!(state.stack_undo.is_empty());
+        state.replace(color, RED);
+        assert_eq!(state.stack_undo.len(), 1);
+        assert_eq!(state.stack[1].style.fill, Some(RED));
+    }
+
+    #[test]
+    fn set_palette_moves_role_colours() {
+        let mut state = state(&[0, 0]);
+        let color = state.stack[0].style.fill.unwrap();
+        state.stack[1].style.fill = Some(BLUE);
+        let role = Some("body".to_string());
+        state.palette.swatches = vec![EditorSwatch::new(color, role.clone())];
+        let mut palette = state.palette.clone();
+        palette.swatches = vec![EditorSwatch::new(RED, role)];
+
+        state.set_palette(palette);
+        assert_eq!(state.stack[0].style.fill, Some(RED));
+        assert_eq!(state.stack[1].style.fill, Some(BLUE));
+        assert_eq!(state.stack_undo.len(), 1);
+    }
+}
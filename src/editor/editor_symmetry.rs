@@ -0,0 +1,259 @@
+use std::f32::consts::TAU;
+
+use macroquad::prelude::draw_line;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::measure_text;
+use macroquad::prelude::screen_height;
+use macroquad::prelude::screen_width;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::GREEN;
+
+use super::EditorElement;
+use super::EditorState;
+use super::EditorValues;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorSymmetries {
+    /// Mirrored across a vertical axis, left and right.
+    Vertical,
+    /// Mirrored across a horizontal axis, top and bottom.
+    Horizontal,
+    /// Repeated the given number of times around a center.
+    Radial(u8),
+}
+
+impl EditorSymmetries {
+    pub fn text(&self) -> String {
+        match self {
+            EditorSymmetries::Vertical => "VERTICAL".to_string(),
+            EditorSymmetries::Horizontal => "HORIZONTAL".to_string(),
+            EditorSymmetries::Radial(n) => format!("RADIAL {}", n),
+        }
+    }
+
+    /// Modes in the order `M` cycles through them.
+    pub fn list() -> [EditorSymmetries; 6] {
+        [
+            EditorSymmetries::Vertical,
+            EditorSymmetries::Horizontal,
+            EditorSymmetries::Radial(3),
+            EditorSymmetries::Radial(4),
+            EditorSymmetries::Radial(6),
+            EditorSymmetries::Radial(8),
+        ]
+    }
+
+    pub fn count(&self) -> usize {
+        match self {
+            EditorSymmetries::Vertical | EditorSymmetries::Horizontal => 2,
+            EditorSymmetries::Radial(n) => *n as usize,
+        }
+    }
+
+    /// Moves `value` from copy `from` to copy `to` of the symmetry around `pivot`.
+    pub fn transform(&self, value: &mut EditorValues, pivot: Vec2, from: usize, to: usize) {
+        match self {
+            EditorSymmetries::Vertical if from != to => value.scale(pivot, Vec2::new(-1.0, 1.0)),
+            EditorSymmetries::Horizontal if from != to => value.scale(pivot, Vec2::new(1.0, -1.0)),
+            EditorSymmetries::Radial(n) => {
+                let angle = TAU * (to as f32 - from as f32) / *n as f32;
+                value.rotate(pivot, angle);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Ties the copies made by a symmetry together: every element sharing `id` is kept in sync.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EditorLink {
+    pub id: usize,
+    pub index: usize,
+    pub symmetry: EditorSymmetries,
+    pub pivot: Vec2,
+}
+
+pub struct EditorSymmetry {}
+
+impl EditorSymmetry {
+    /// Cycles off, then through every mode, then off again.
+    pub fn next(state: &mut EditorState) {
+        let list = EditorSymmetries::list();
+        state.symmetry = match state.symmetry {
+            None => Some(list[0]),
+            Some(symmetry) => list
+                .iter()
+                .position(|&i| i == symmetry)
+                .and_then(|i| list.get(i + 1))
+                .copied(),
+        };
+    }
+
+    /// The element followed by its linked copies when symmetry is on.
+    pub fn copies(state: &EditorState, element: EditorElement) -> Vec<EditorElement> {
+        let Some(symmetry) = state.symmetry else {
+            return vec![element];
+        };
+        let id = state
+            .stack
+            .iter()
+            .filter_map(|i| i.link.map(|link| link.id + 1))
+            .max()
+            .unwrap_or(0);
+        let pivot = Vec2::new(screen_width(), screen_height()) / 2.0;
+        (0..symmetry.count())
+            .map(|index| {
+                let mut copy = element;
+                symmetry.transform(&mut copy.value, pivot, 0, index);
+                copy.link = Some(EditorLink {
+                    id,
+                    index,
+                    symmetry,
+                    pivot,
+                });
+                copy
+            })
+            .collect()
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        let Some(symmetry) = state.symmetry else {
+            return;
+        };
+        let width = screen_width();
+        let height = screen_height();
+        let center = Vec2::new(width, height) / 2.0;
+        let color = GREEN.with_alpha(0.3);
+        match symmetry {
+            EditorSymmetries::Vertical => draw_line(center.x, 0.0, center.x, height, 1.0, color),
+            EditorSymmetries::Horizontal => draw_line(0.0, center.y, width, center.y, 1.0, color),
+            EditorSymmetries::Radial(n) => {
+                for i in 0..n {
+                    let angle = TAU * i as f32 / n as f32 - TAU / 4.0;
+                    let end = center + Vec2::from_angle(angle) * width.max(height);
+                    draw_line(center.x, center.y, end.x, end.y, 1.0, color);
+                }
+            }
+        }
+        let text = format!("SYMMETRY {}", symmetry.text());
+        let text_dimensions = measure_text(&text, None, 18, 1.0);
+        let x = center.x - text_dimensions.width / 2.0;
+        draw_text(&text, x, 20.0, 18.0, GREEN);
+    }
+
+    /// Finds linked elements edited since the last frame, by any tool, and updates their copies.
+    /// Copies are matched by their link rather than their position, so reordering the stack is
+    /// not an edit. Links whose copies all changed together, like a nudge of the whole drawing,
+    /// are left as they are instead of being overwritten by one of them.
+    pub fn actions(state: &mut EditorState) {
+        let previous = std::mem::take(&mut state.stack_previous);
+        let key = |element: &EditorElement| element.link.map(|i| (i.id, i.index));
+        let changed: Vec<usize> = (0..state.stack.len())
+            .filter(|&i| {
+                let element = &state.stack[i];
+                key(element).is_some_and(|link| {
+                    previous
+                        .iter()
+                        .find(|j| key(j) == Some(link))
+                        .is_some_and(|j| j != element)
+                })
+            })
+            .collect();
+        let mut synced = Vec::new();
+        for &i in changed.iter() {
+            let Some(link) = state.stack[i].link else {
+                continue;
+            };
+            if synced.contains(&link.id) {
+                continue;
+            }
+            synced.push(link.id);
+            let all = (0..state.stack.len())
+                .filter(|&j| state.stack[j].link.is_some_and(|j| j.id == link.id))
+                .all(|j| changed.contains(&j));
+            if !all {
+                EditorSymmetry::sync(state, i);
+            }
+        }
+        state.stack_previous = state.stack.clone();
+    }
+
+    fn sync(state: &mut EditorState, source: usize) {
+        let element = state.stack[source];
+        let Some(link) = element.link else {
+            return;
+        };
+        for (i, copy) in state.stack.iter_mut().enumerate() {
+            let Some(copy_link) = copy.link.filter(|i| i.id == link.id) else {
+                continue;
+            };
+            if i != source {
+                let mut value = element.value;
+                link.symmetry
+                    .transform(&mut value, link.pivot, link.index, copy_link.index);
+                copy.value = value;
+                copy.style = element.style;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+
+    use super::EditorElement;
+    use super::EditorLink;
+    use super::EditorState;
+    use super::EditorSymmetries;
+    use super::EditorSymmetry;
+    use super::EditorValues;
+
+    fn state() -> EditorState {
+        let mut state = EditorState::new();
+        for index in 0..2 {
+            let x = if index == 0 { -10.0 } else { 10.0 };
+            let point = Vec2::new(x, 5.0);
+            let mut element = EditorElement::new(EditorValues::Marker { point }, state.style());
+            element.link = Some(EditorLink {
+                id: 0,
+                index,
+                symmetry: EditorSymmetries::Vertical,
+                pivot: Vec2::ZERO,
+            });
+            state.stack.push(element);
+        }
+        EditorSymmetry::actions(&mut state);
+        state
+    }
+
+    fn x(state: &EditorState, index: usize) -> f32 {
+        let element = state.stack.iter().find(|i| i.link.unwrap().index == index);
+        element.unwrap().value.bounds().x
+    }
+
+    #[test]
+    fn edits_are_mirrored() {
+        let mut state = state();
+        state.stack[0].value.translate(Vec2::new(-5.0, 0.0));
+        EditorSymmetry::actions(&mut state);
+        assert_eq!((x(&state, 0), x(&state, 1)), (-15.0, 15.0));
+
+        // Moving both copies is kept as it is.
+        for element in state.stack.iter_mut() {
+            element.value.translate(Vec2::new(3.0, 0.0));
+        }
+        EditorSymmetry::actions(&mut state);
+        assert_eq!((x(&state, 0), x(&state, 1)), (-12.0, 18.0));
+    }
+
+    #[test]
+    fn reordering_is_not_an_edit() {
+        let mut state = state();
+        state.stack[1].value.translate(Vec2::new(0.001, 0.0));
+        state.stack_previous = state.stack.clone();
+        state.stack.swap(0, 1);
+        EditorSymmetry::actions(&mut state);
+        assert_eq!((x(&state, 0), x(&state, 1)), (-10.0, 10.001));
+    }
+}
@@ -0,0 +1,113 @@
+use super::EditorElement;
+use super::EditorExport;
+use super::EditorState;
+
+pub const TAGS_MAX: usize = 8;
+
+/// Names and tags of elements, kept in `state.names` and `state.tags` and referenced by index
+/// so elements stay `Copy`. Both lists only grow, which keeps undo consistent; the limit is on
+/// the tags of a single element, not on how many exist.
+pub struct EditorTag {}
+
+impl EditorTag {
+    pub fn name<'a>(state: &'a EditorState, element: &EditorElement) -> Option<&'a str> {
+        element.name.map(|i| state.names[i].as_str())
+    }
+
+    pub fn tags<'a>(state: &'a EditorState, element: &EditorElement) -> Vec<&'a str> {
+        element
+            .tags
+            .iter()
+            .flatten()
+            .map(|&i| state.tags[i].as_str())
+            .collect()
+    }
+
+    fn intern(list: &mut Vec<String>, text: String) -> usize {
+        match list.iter().position(|i| *i == text) {
+            Some(i) => i,
+            None => {
+                list.push(text);
+                list.len() - 1
+            }
+        }
+    }
+
+    /// Name from typed text, `none` or nothing clears it.
+    pub fn parse_name(state: &mut EditorState, text: &str) -> Result<Option<usize>, String> {
+        let text = text.trim();
+        if text.is_empty() || text.eq_ignore_ascii_case("none") {
+            return Ok(None);
+        }
+        let name = EditorExport::identifier(text).ok_or("EXPECTED A NAME")?;
+        Ok(Some(EditorTag::intern(&mut state.names, name)))
+    }
+
+    /// Tags from typed text separated by commas or spaces, `none` or nothing clears them.
+    pub fn parse_tags(
+        state: &mut EditorState,
+        text: &str,
+    ) -> Result<[Option<usize>; TAGS_MAX], String> {
+        let text = text.trim();
+        let mut tags = [None; TAGS_MAX];
+        if text.eq_ignore_ascii_case("none") {
+            return Ok(tags);
+        }
+        let mut count = 0;
+        for tag in text.split(|c: char| c.is_whitespace() || c == ',') {
+            let Some(tag) = EditorExport::identifier(tag) else {
+                continue;
+            };
+            let tag = Some(EditorTag::intern(&mut state.tags, tag));
+            if tags[..count].contains(&tag) {
+                continue;
+            }
+            if count == TAGS_MAX {
+                return Err(format!("AT MOST {} TAGS", TAGS_MAX));
+            }
+            tags[count] = tag;
+            count += 1;
+        }
+        Ok(tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EditorState;
+    use super::EditorTag;
+    use super::TAGS_MAX;
+
+    #[test]
+    fn names() {
+        let mut state = EditorState::new();
+        assert_eq!(EditorTag::parse_name(&mut state, "  "), Ok(None));
+        assert_eq!(EditorTag::parse_name(&mut state, "None"), Ok(None));
+        assert_eq!(EditorTag::parse_name(&mut state, "Left Eye"), Ok(Some(0)));
+        assert_eq!(EditorTag::parse_name(&mut state, "left-eye"), Ok(Some(0)));
+        assert_eq!(EditorTag::parse_name(&mut state, "1st"), Ok(Some(1)));
+        assert_eq!(state.names, vec!["left_eye", "_1st"]);
+        assert!(EditorTag::parse_name(&mut state, "!!").is_err());
+    }
+
+    #[test]
+    fn tags() {
+        let mut state = EditorState::new();
+        let mut parse = |text: &str| {
+            EditorTag::parse_tags(&mut state, text).map(|i| i.iter().flatten().count())
+        };
+        assert_eq!(parse("solid, Hot solid"), Ok(2));
+        assert_eq!(parse("hot"), Ok(1));
+        assert_eq!(parse("none"), Ok(0));
+
+        // Any number of tags can exist, only a single element is limited.
+        for i in 0..100 {
+            assert_eq!(parse(&format!("tag{}", i)), Ok(1));
+        }
+        let text: Vec<_> = (0..TAGS_MAX).map(|i| format!("tag{}", i)).collect();
+        assert_eq!(parse(&text.join(" ")), Ok(TAGS_MAX));
+        assert!(parse(&format!("{} solid", text.join(" "))).is_err());
+        assert_eq!(state.tags.len(), 102);
+        assert_eq!(&state.tags[..3], ["solid", "hot", "tag0"]);
+    }
+}
@@ -0,0 +1,190 @@
+use macroquad::prelude::draw_circle;
+use macroquad::prelude::draw_circle_lines;
+use macroquad::prelude::draw_line;
+use macroquad::prelude::draw_rectangle;
+use macroquad::prelude::draw_rectangle_lines;
+use macroquad::prelude::draw_text;
+use macroquad::prelude::is_key_down;
+use macroquad::prelude::is_mouse_button_down;
+use macroquad::prelude::is_mouse_button_pressed;
+use macroquad::prelude::is_mouse_button_released;
+use macroquad::prelude::mouse_position;
+use macroquad::prelude::KeyCode;
+use macroquad::prelude::MouseButton;
+use macroquad::prelude::Rect;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::BLACK;
+use macroquad::prelude::YELLOW;
+
+use super::EditorState;
+
+pub const HANDLE_SIZE: f32 = 5.0;
+pub const HANDLE_OFFSET: f32 = 20.0;
+pub const ROTATION_SNAP: f32 = 15.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorTransforms {
+    Rotate,
+    /// Side of the bounding box being dragged, `-1`, `0` or `1` per axis.
+    Scale(i8, i8),
+}
+
+pub struct EditorTransform {}
+
+impl EditorTransform {
+    pub fn list(state: &EditorState) -> Vec<(EditorTransforms, Vec2)> {
+        if state.draw || state.vertex {
+            return Vec::new();
+        }
+        let Some(bounds) = state.selection_bounds() else {
+            return Vec::new();
+        };
+        let rotate = Vec2::new(bounds.center().x, bounds.y - HANDLE_OFFSET);
+        let mut list = vec![(EditorTransforms::Rotate, rotate)];
+        for y in -1..=1 {
+            for x in -1..=1 {
+                if x != 0 || y != 0 {
+                    let handle = EditorTransform::handle(bounds, x, y);
+                    list.push((EditorTransforms::Scale(x, y), handle));
+                }
+            }
+        }
+        list
+    }
+
+    fn handle(bounds: Rect, x: i8, y: i8) -> Vec2 {
+        bounds.center() + Vec2::new(x as f32 * bounds.w, y as f32 * bounds.h) / 2.0
+    }
+
+    fn factor(state: &mut EditorState, bounds: Rect, x: i8, y: i8) -> (Vec2, Vec2) {
+        let position = state.position();
+        let handle = EditorTransform::handle(bounds, x, y);
+        let pivot = if is_key_down(KeyCode::LeftAlt) {
+            bounds.center()
+        } else {
+            EditorTransform::handle(bounds, -x, -y)
+        };
+        let mut factor = Vec2::ONE;
+        if x != 0 && (handle.x - pivot.x).abs() > f32::EPSILON {
+            factor.x = (position.x - pivot.x) / (handle.x - pivot.x);
+        }
+        if y != 0 && (handle.y - pivot.y).abs() > f32::EPSILON {
+            factor.y = (position.y - pivot.y) / (handle.y - pivot.y);
+        }
+        if is_key_down(KeyCode::LeftShift) {
+            let uniform = match (x, y) {
+                (0, _) => factor.y,
+                (_, 0) => factor.x,
+                _ if factor.x.abs() > factor.y.abs() => factor.x,
+                _ => factor.y,
+            };
+            factor = Vec2::splat(uniform);
+        }
+        (pivot, factor)
+    }
+
+    pub fn find(state: &EditorState) -> Option<EditorTransforms> {
+        let position: Vec2 = mouse_position().into();
+        EditorTransform::list(state)
+            .into_iter()
+            .find(|(_, handle)| position.distance(*handle) <= HANDLE_SIZE * 1.5)
+            .map(|(transform, _)| transform)
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        let Some(bounds) = state
+            .selection_bounds()
+            .filter(|_| !state.draw && !state.vertex)
+        else {
+            return;
+        };
+        let color = YELLOW.with_alpha(0.5);
+        draw_rectangle_lines(bounds.x, bounds.y, bounds.w, bounds.h, 1.0, color);
+
+        for (transform, handle) in EditorTransform::list(state) {
+            match transform {
+                EditorTransforms::Rotate => {
+                    let x = bounds.center().x;
+                    draw_line(x, bounds.y, handle.x, handle.y, 1.0, color);
+                    draw_circle(handle.x, handle.y, HANDLE_SIZE, BLACK);
+                    draw_circle_lines(handle.x, handle.y, HANDLE_SIZE, 1.0, YELLOW);
+                }
+                EditorTransforms::Scale(..) => {
+                    let x = handle.x - HANDLE_SIZE;
+                    let y = handle.y - HANDLE_SIZE;
+                    let size = HANDLE_SIZE * 2.0;
+                    draw_rectangle(x, y, size, size, BLACK);
+                    draw_rectangle_lines(x, y, size, size, 1.0, YELLOW);
+                }
+            }
+        }
+
+        if let (Some(EditorTransforms::Rotate), Some(pivot), Some(rotation)) =
+            (state.drag_transform, state.drag_pivot, state.drag_rotation)
+        {
+            let angle = EditorTransform::angle(pivot, rotation);
+            let text = format!("{:.0} DEG", angle.to_degrees());
+            draw_text(&text, bounds.right() + 10.0, bounds.y, 18.0, YELLOW);
+        }
+    }
+
+    fn angle(pivot: Vec2, rotation: f32) -> f32 {
+        let position: Vec2 = mouse_position().into();
+        let angle = (position - pivot).to_angle() - rotation;
+        if is_key_down(KeyCode::LeftShift) {
+            let snap = ROTATION_SNAP.to_radians();
+            (angle / snap).round() * snap
+        } else {
+            angle
+        }
+    }
+
+    pub fn actions(state: &mut EditorState) {
+        let position: Vec2 = mouse_position().into();
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            if let Some(transform) = EditorTransform::find(state) {
+                let Some(bounds) = state.selection_bounds() else {
+                    return;
+                };
+                state.begin();
+                let pivot = bounds.center();
+                state.drag_transform = Some(transform);
+                state.drag_pivot = Some(pivot);
+                state.drag_rotation = Some((position - pivot).to_angle());
+                state.drag_bounds = Some(bounds);
+                state.drag_original = state.selection.iter().map(|&i| state.stack[i]).collect();
+            }
+        }
+
+        if is_mouse_button_down(MouseButton::Left) {
+            if let (Some(EditorTransforms::Rotate), Some(pivot), Some(rotation)) =
+                (state.drag_transform, state.drag_pivot, state.drag_rotation)
+            {
+                let angle = EditorTransform::angle(pivot, rotation);
+                for (k, &i) in state.selection.iter().enumerate() {
+                    state.stack[i] = state.drag_original[k];
+                    state.stack[i].value.rotate(pivot, angle);
+                }
+            }
+            if let (Some(EditorTransforms::Scale(x, y)), Some(bounds)) =
+                (state.drag_transform, state.drag_bounds)
+            {
+                let (pivot, factor) = EditorTransform::factor(state, bounds, x, y);
+                for (k, &i) in state.selection.iter().enumerate() {
+                    state.stack[i] = state.drag_original[k];
+                    state.stack[i].value.scale(pivot, factor);
+                }
+            }
+        }
+
+        if is_mouse_button_released(MouseButton::Left) && state.drag_transform.is_some() {
+            state.commit();
+            state.drag_transform = None;
+            state.drag_pivot = None;
+            state.drag_rotation = None;
+            state.drag_bounds = None;
+            state.drag_original.clear();
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use macroquad::prelude::draw_circle;
+use macroquad::prelude::draw_circle_lines;
+use macroquad::prelude::is_key_down;
+use macroquad::prelude::is_mouse_button_down;
+use macroquad::prelude::is_mouse_button_pressed;
+use macroquad::prelude::is_mouse_button_released;
+use macroquad::prelude::mouse_position;
+use macroquad::prelude::KeyCode;
+use macroquad::prelude::MouseButton;
+use macroquad::prelude::Vec2;
+use macroquad::prelude::BLACK;
+use macroquad::prelude::GREEN;
+use macroquad::prelude::YELLOW;
+
+use super::EditorLayer;
+use super::EditorState;
+
+pub const VERTEX_SIZE: f32 = 4.0;
+pub const VERTEX_CONNECTED: f32 = 0.5;
+
+/// Control points of every element while vertex mode is on. A dragged point takes the points
+/// lying on it along so closed outlines stay closed.
+pub struct EditorVertex {}
+
+impl EditorVertex {
+    /// Element index, point index and position of every control point.
+    pub fn list(state: &EditorState) -> Vec<(usize, usize, Vec2)> {
+        if state.draw || !state.vertex {
+            return Vec::new();
+        }
+        state
+            .stack
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| EditorLayer::editable(state, *i))
+            .flat_map(|(i, element)| {
+                let points = element.value.points();
+                points
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(k, point)| (i, k, point))
+            })
+            .collect()
+    }
+
+    pub fn find(state: &EditorState) -> Option<(usize, usize)> {
+        let position: Vec2 = mouse_position().into();
+        EditorVertex::list(state)
+            .into_iter()
+            .rev()
+            .find(|(_, _, point)| position.distance(*point) <= VERTEX_SIZE * 1.5)
+            .map(|(i, k, _)| (i, k))
+    }
+
+    /// Points lying on the point `k` of element `i`, itself included. Only that point is
+    /// returned when connected editing is off or `Alt` is held.
+    pub fn connected(state: &EditorState, i: usize, k: usize) -> Vec<(usize, usize)> {
+        if !state.connect || is_key_down(KeyCode::LeftAlt) {
+            return vec![(i, k)];
+        }
+        let point = state.stack[i].value.points()[k];
+        EditorVertex::list(state)
+            .into_iter()
+            .filter(|(_, _, p)| p.distance(point) <= VERTEX_CONNECTED)
+            .map(|(i, k, _)| (i, k))
+            .collect()
+    }
+
+    pub fn hover(state: &EditorState) -> bool {
+        !state.drag_points.is_empty() || EditorVertex::find(state).is_some()
+    }
+
+    pub fn draw(state: &mut EditorState) {
+        let hover = if state.drag_points.is_empty() {
+            EditorVertex::find(state)
+                .map(|(i, k)| EditorVertex::connected(state, i, k))
+                .unwrap_or_default()
+        } else {
+            state.drag_points.clone()
+        };
+        for (i, k, point) in EditorVertex::list(state) {
+            let color = if hover.contains(&(i, k)) {
+                GREEN
+            } else {
+                YELLOW
+            };
+            draw_circle(point.x, point.y, VERTEX_SIZE, BLACK);
+            draw_circle_lines(point.x, point.y, VERTEX_SIZE, 1.0, color);
+        }
+    }
+
+    pub fn actions(state: &mut EditorState) {
+        if is_mouse_button_pressed(MouseButton::Left) {
+            if let Some((i, k)) = EditorVertex::find(state) {
+                state.begin();
+                state.drag_points = EditorVertex::connected(state, i, k);
+                state.drag_original = state
+                    .drag_points
+                    .iter()
+                    .map(|&(i, _)| state.stack[i])
+                    .collect();
+            }
+        }
+
+        if is_mouse_button_down(MouseButton::Left) && !state.drag_points.is_empty() {
+            // Restore first so the points do not snap to their own previous position.
+            for (n, &(i, _)) in state.drag_points.iter().enumerate() {
+                state.stack[i] = state.drag_original[n];
+            }
+            let position = state.position();
+            for &(i, k) in state.drag_points.iter() {
+                state.stack[i].value.point(k, position);
+            }
+        }
+
+        if is_mouse_button_released(MouseButton::Left) && !state.drag_points.is_empty() {
+            for &(i, _) in state.drag_points.iter() {
+                state.stack[i].value.convex();
+            }
+            state.commit();
+            state.drag_points.clear();
+            state.drag_original.clear();
+        }
+    }
+}
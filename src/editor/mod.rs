@@ -1,21 +1,76 @@
+#[allow(clippy::module_inception)]
 mod editor;
 pub use editor::*;
 
+pub mod editor_align;
+pub use editor_align::*;
+
 pub mod editor_button;
 pub use editor_button::*;
 
 pub mod editor_buttons;
 pub use editor_buttons::*;
 
+pub mod editor_clipboard;
+pub use editor_clipboard::*;
+
 pub mod editor_element;
 pub use editor_element::*;
 
 pub mod editor_elements;
 pub use editor_elements::*;
 
+pub mod editor_export;
+pub use editor_export::*;
+
+pub mod editor_group;
+pub use editor_group::*;
+
 pub mod editor_helps;
 pub use editor_helps::*;
 
+pub mod editor_hull;
+pub use editor_hull::*;
+
+pub mod editor_input;
+pub use editor_input::*;
+
+pub mod editor_inspector;
+pub use editor_inspector::*;
+
+pub mod editor_json;
+pub use editor_json::*;
+
+pub mod editor_layer;
+pub use editor_layer::*;
+
+pub mod editor_metrics;
+pub use editor_metrics::*;
+
+pub mod editor_palette;
+pub use editor_palette::*;
+
+pub mod editor_picker;
+pub use editor_picker::*;
+
+pub mod editor_pivot;
+pub use editor_pivot::*;
+
+pub mod editor_repeat;
+pub use editor_repeat::*;
+
+pub mod editor_symmetry;
+pub use editor_symmetry::*;
+
+pub mod editor_tag;
+pub use editor_tag::*;
+
+pub mod editor_transform;
+pub use editor_transform::*;
+
+pub mod editor_vertex;
+pub use editor_vertex::*;
+
 mod editor_state;
 pub use editor_state::*;
 